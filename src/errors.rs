@@ -17,6 +17,12 @@ pub enum NovaError {
   /// returned if the supplied input is not of the right length
   #[error("InvalidInputLength")]
   InvalidInputLength,
+  /// returned if the supplied input is not consistent with itself, at the given index
+  #[error("InconsistentInput: index {index}")]
+  InconsistentInput {
+    /// The first index at which the inconsistency was found
+    index: usize,
+  },
   /// returned if the supplied witness is not of the right length
   #[error("InvalidWitnessLength")]
   InvalidWitnessLength,
@@ -68,12 +74,44 @@ pub enum NovaError {
     /// The reason for circuit synthesis failure
     reason: String,
   },
+  /// returned when there is an error serializing or deserializing an object
+  #[error("SerializationError: {reason}")]
+  SerializationError {
+    /// The reason for the serialization failure
+    reason: String,
+  },
   /// returned when there is an error creating a digest
   #[error("DigestError")]
   DigestError,
   /// returned when the prover cannot prove the provided statement due to completeness error
   #[error("InternalError")]
   InternalError,
+  /// returned when a vector has more entries than the commitment key has generators
+  #[error("CommitmentKeyTooSmall: required {required}, available {available}")]
+  CommitmentKeyTooSmall {
+    /// The number of generators needed
+    required: usize,
+    /// The number of generators in the commitment key
+    available: usize,
+  },
+  /// returned when a deadline passes before proving completes
+  #[error("TimeoutExceeded")]
+  TimeoutExceeded,
+  /// returned when a dense form of a structure would have more entries than allowed
+  #[error("TooLargeForDenseForm: {size} entries")]
+  TooLargeForDenseForm {
+    /// The number of entries the dense form would have
+    size: usize,
+  },
+  /// returned when the running instance no longer matches the expected accumulator
+  #[error("AccumulatorDrift: {reason}")]
+  AccumulatorDrift {
+    /// The reason for the mismatch
+    reason: String,
+  },
+  /// returned when the IVC chain is started from a running instance that is not the initial accumulator
+  #[error("NonTrivialInitialAccumulator")]
+  NonTrivialInitialAccumulator,
 }
 
 impl From<SynthesisError> for NovaError {
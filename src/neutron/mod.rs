@@ -240,6 +240,11 @@ where
 
   /// Updates the provided `RecursiveSNARK` by executing a step of the incremental computation
   pub fn prove_step(&mut self, pp: &PublicParams<E1, E2, C>, c: &C) -> Result<(), NovaError> {
+    // the chain must start from the initial accumulator
+    if self.i <= 1 && !self.r_U.is_trivial() {
+      return Err(NovaError::NonTrivialInitialAccumulator);
+    }
+
     // first step was already done in the constructor
     if self.i == 0 {
       self.i = 1;
@@ -374,6 +379,35 @@ where
   pub fn num_steps(&self) -> usize {
     self.i
   }
+
+  /// Serializes the `RecursiveSNARK` into a checkpoint from which proving can be resumed with `load`.
+  ///
+  /// The checkpoint is the `bincode` encoding of `self`, prefixed by the CRC32 checksum
+  /// of that encoding in 4 little-endian bytes.
+  pub fn save(&self) -> Vec<u8> {
+    let payload = bincode::serialize(self).expect("Serialization error");
+    let checksum = crc32fast::hash(&payload);
+    [checksum.to_le_bytes().to_vec(), payload].concat()
+  }
+
+  /// Restores a `RecursiveSNARK` from a checkpoint produced by `save`,
+  /// after checking that its checksum matches
+  pub fn load(bytes: &[u8]) -> Result<Self, NovaError> {
+    if bytes.len() < 4 {
+      return Err(NovaError::InvalidInputLength);
+    }
+    let (checksum, payload) = bytes.split_at(4);
+
+    if crc32fast::hash(payload).to_le_bytes() != checksum {
+      return Err(NovaError::SerializationError {
+        reason: "checkpoint checksum mismatch".to_string(),
+      });
+    }
+
+    bincode::deserialize(payload).map_err(|e| NovaError::SerializationError {
+      reason: e.to_string(),
+    })
+  }
 }
 
 #[cfg(test)]
@@ -578,6 +612,57 @@ mod tests {
     assert_eq!(zn, vec![<E1 as Engine>::Scalar::from(0x2aaaaa3u64)]);
   }
 
+  fn test_ivc_checkpoint_with<E1, E2>()
+  where
+    E1: Engine<Base = <E2 as Engine>::Scalar>,
+    E2: Engine<Base = <E1 as Engine>::Scalar>,
+  {
+    let circuit = CubicCircuit::default();
+    let z0 = [<E1 as Engine>::Scalar::ONE];
+
+    // produce public parameters
+    let pp = PublicParams::<E1, E2, CubicCircuit<<E1 as Engine>::Scalar>>::setup(
+      &circuit,
+      &*default_ck_hint(),
+      &*default_ck_hint(),
+    )
+    .unwrap();
+
+    let mut recursive_snark =
+      RecursiveSNARK::<E1, E2, CubicCircuit<<E1 as Engine>::Scalar>>::new(&pp, &circuit, &z0)
+        .unwrap();
+    for _ in 0..2 {
+      recursive_snark.prove_step(&pp, &circuit).unwrap();
+    }
+
+    // resume from a checkpoint and keep proving
+    let checkpoint = recursive_snark.save();
+    let mut resumed =
+      RecursiveSNARK::<E1, E2, CubicCircuit<<E1 as Engine>::Scalar>>::load(&checkpoint).unwrap();
+    assert_eq!(resumed.num_steps(), 2);
+    resumed.prove_step(&pp, &circuit).unwrap();
+    assert!(resumed.verify(&pp, 3, &z0).is_ok());
+
+    // corrupted or truncated checkpoints are rejected
+    let mut corrupted = checkpoint.clone();
+    let last = corrupted.len() - 1;
+    corrupted[last] ^= 1;
+    assert!(matches!(
+      RecursiveSNARK::<E1, E2, CubicCircuit<<E1 as Engine>::Scalar>>::load(&corrupted),
+      Err(NovaError::SerializationError { .. })
+    ));
+    assert!(matches!(
+      RecursiveSNARK::<E1, E2, CubicCircuit<<E1 as Engine>::Scalar>>::load(&checkpoint[..3]),
+      Err(NovaError::InvalidInputLength)
+    ));
+  }
+
+  #[test]
+  fn test_ivc_checkpoint() {
+    test_ivc_checkpoint_with::<PallasEngine, VestaEngine>();
+    test_ivc_checkpoint_with::<Bn256EngineKZG, GrumpkinEngine>();
+  }
+
   #[test]
   fn test_ivc_nontrivial_neutron() {
     test_ivc_nontrivial_with::<PallasEngine, VestaEngine>();
@@ -631,6 +716,42 @@ mod tests {
     test_ivc_base_with::<Secp256k1Engine, Secq256k1Engine>();
   }
 
+  fn test_ivc_nontrivial_initial_accumulator_with<E1, E2>()
+  where
+    E1: Engine<Base = <E2 as Engine>::Scalar>,
+    E2: Engine<Base = <E1 as Engine>::Scalar>,
+  {
+    let test_circuit1 = CubicCircuit::<<E1 as Engine>::Scalar>::default();
+
+    // produce public parameters
+    let pp = PublicParams::<E1, E2, CubicCircuit<<E1 as Engine>::Scalar>>::setup(
+      &test_circuit1,
+      &*default_ck_hint(),
+      &*default_ck_hint(),
+    )
+    .unwrap();
+
+    let mut recursive_snark = RecursiveSNARK::<E1, E2, CubicCircuit<<E1 as Engine>::Scalar>>::new(
+      &pp,
+      &test_circuit1,
+      &[<E1 as Engine>::Scalar::ONE],
+    )
+    .unwrap();
+
+    // a running instance with a folded-in claim is not a valid starting point
+    recursive_snark.r_U.T = <E1 as Engine>::Scalar::ONE;
+
+    let res = recursive_snark.prove_step(&pp, &test_circuit1);
+    assert!(matches!(res, Err(NovaError::NonTrivialInitialAccumulator)));
+  }
+
+  #[test]
+  fn test_ivc_nontrivial_initial_accumulator() {
+    test_ivc_nontrivial_initial_accumulator_with::<PallasEngine, VestaEngine>();
+    test_ivc_nontrivial_initial_accumulator_with::<Bn256EngineKZG, GrumpkinEngine>();
+    test_ivc_nontrivial_initial_accumulator_with::<Secp256k1Engine, Secq256k1Engine>();
+  }
+
   fn test_setup_with<E1, E2>()
   where
     E1: Engine<Base = <E2 as Engine>::Scalar>,
@@ -1,18 +1,44 @@
 //! This module implements a non-interactive folding scheme from NeutronNova
 #![allow(non_snake_case)]
 use crate::{
-  constants::NUM_CHALLENGE_BITS,
+  constants::{BN_LIMB_WIDTH, BN_N_LIMBS, NUM_CHALLENGE_BITS},
   errors::NovaError,
-  neutron::relation::{FoldedInstance, FoldedWitness, Structure},
+  frontend::{num::AllocatedNum, shape_cs::ShapeCS, ConstraintSystem, SynthesisError},
+  gadgets::{
+    ecc::AllocatedNonnativePoint,
+    nonnative::{
+      bignat::limbs_to_nat,
+      util::{f_to_nat, nat_to_f},
+    },
+    utils::to_bignat_repr,
+  },
+  neutron::{
+    circuit::{
+      nifs::AllocatedNIFS, r1cs::AllocatedNonnativeR1CSInstance, relation::AllocatedFoldedInstance,
+    },
+    relation::{FoldedInstance, FoldedWitness, Structure},
+  },
   r1cs::{R1CSInstance, R1CSWitness},
-  spartan::polys::{power::PowPolynomial, univariate::UniPoly},
-  traits::{commitment::CommitmentEngineTrait, AbsorbInRO2Trait, Engine, RO2Constants, ROTrait},
+  spartan::polys::{
+    power::PowPolynomial,
+    univariate::{CompressedUniPoly, UniPoly},
+  },
+  traits::{
+    commitment::{CommitmentEngineTrait, CommitmentTrait},
+    AbsorbInRO2Trait, Engine, RO2Constants, RO2ConstantsCircuit, ROTrait,
+  },
   Commitment, CommitmentKey, CE,
 };
-use ff::Field;
+use bincode::Options;
+use ff::{Field, PrimeField};
 use rand_core::OsRng;
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+  io::{Read, Write},
+  time::Instant,
+};
 
 /// An NIFS message from NeutronNova's folding scheme
 #[allow(clippy::upper_case_acronyms)]
@@ -23,6 +49,117 @@ pub struct NIFS<E: Engine> {
   pub(crate) poly: UniPoly<E::Scalar>,
 }
 
+/// A round of the sum-check protocol that runs inside `NIFS`,
+/// exposed so that the sum-check can be handed to an external verifier
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SumCheckRound<E: Engine> {
+  /// Coefficients of the round polynomial, starting with the constant term
+  pub univariate_poly: Vec<E::Scalar>,
+  /// The verifier's challenge for the round
+  pub challenge: E::Scalar,
+}
+
+/// A `NIFS` without the linear term of its sum-check polynomial, as produced by `NIFS::compress`
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct CompressedNIFS<E: Engine> {
+  comm_E: Commitment<E>,
+  poly: CompressedUniPoly<E::Scalar>,
+}
+
+impl<E: Engine> CompressedNIFS<E> {
+  /// Recovers the `NIFS` folding `U2` into `U1`. The omitted linear term follows from
+  /// `poly(0) + poly(1) = (1 - rho) * U1.T`, where `rho` is obtained by replaying the transcript,
+  /// so `pp_digest`, `U1`, and `U2` must be the ones provided to `NIFS::prove`
+  pub fn decompress(
+    &self,
+    ro_consts: &RO2Constants<E>,
+    pp_digest: &E::Scalar,
+    U1: &FoldedInstance<E>,
+    U2: &R1CSInstance<E>,
+  ) -> NIFS<E> {
+    let (_ro, rho) = NIFS::transcript_up_to_rho(ro_consts, pp_digest, U2, &self.comm_E);
+    let T = (E::Scalar::ONE - rho) * U1.T;
+    NIFS {
+      comm_E: self.comm_E,
+      poly: self.poly.decompress(&T),
+    }
+  }
+}
+
+/// The number of scalars and commitments in a `NIFS`, as reported by `NIFS::size_in_field_elements`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NIFSSize {
+  /// Number of scalar field elements
+  pub num_scalars: usize,
+  /// Number of commitments
+  pub num_commitments: usize,
+}
+
+/// The inputs of the sum-fold step of `NIFS::prove`, which computes the sum-check polynomial,
+/// as handed to `NIFS::prove_with_external_sumfold`; `E1` and `E2` have `left + right` entries,
+/// and the remaining vectors have `left * right` entries
+pub struct SumFoldInputs<'a, E: Engine> {
+  /// The challenge that combines the running and the incoming claims
+  pub rho: E::Scalar,
+  /// The sizes of the two halves of the power polynomial's split evaluations
+  pub left: usize,
+  /// See `left`
+  pub right: usize,
+  /// The running instance's power polynomial in split form
+  pub E1: &'a [E::Scalar],
+  /// `A * z1` for the running witness `z1`
+  pub Az1: &'a [E::Scalar],
+  /// `B * z1` for the running witness `z1`
+  pub Bz1: &'a [E::Scalar],
+  /// `C * z1` for the running witness `z1`
+  pub Cz1: &'a [E::Scalar],
+  /// The incoming instance's power polynomial in split form
+  pub E2: &'a [E::Scalar],
+  /// `A * z2` for the incoming witness `z2`
+  pub Az2: &'a [E::Scalar],
+  /// `B * z2` for the incoming witness `z2`
+  pub Bz2: &'a [E::Scalar],
+  /// `C * z2` for the incoming witness `z2`
+  pub Cz2: &'a [E::Scalar],
+}
+
+/// The output of the sum-fold step of `NIFS::prove`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SumFoldOutputs<E: Engine> {
+  /// The sum-check polynomial's evaluations at 0, 2, 3, 4, and 5
+  pub evals: [E::Scalar; 5],
+}
+
+/// The part of `NIFS::prove` that depends only on the instance-witness pair being folded in
+struct PreparedStep<E: Engine> {
+  ro: E::RO2,
+  rho: E::Scalar,
+  E: Vec<E::Scalar>,
+  r_E: E::Scalar,
+  comm_E: Commitment<E>,
+  Az2: Vec<E::Scalar>,
+  Bz2: Vec<E::Scalar>,
+  Cz2: Vec<E::Scalar>,
+}
+
+/// A lazy iterator over the NIFS messages of a chain of folding steps, returned by `NIFS::prove_incremental`
+pub struct IncrementalNIFS<'a, E: Engine, I> {
+  ck: &'a CommitmentKey<E>,
+  ro_consts: &'a RO2Constants<E>,
+  pp_digest: E::Scalar,
+  S: &'a Structure<E>,
+  U: FoldedInstance<E>,
+  W: FoldedWitness<E>,
+  steps: I,
+  next: Option<(
+    R1CSInstance<E>,
+    R1CSWitness<E>,
+    Result<PreparedStep<E>, NovaError>,
+  )>,
+  failed: bool,
+}
+
 impl<E: Engine> NIFS<E> {
   /// Computes the evaluations of the sum-check polynomial at 0, 2, 3, and 4
   #[inline]
@@ -207,6 +344,170 @@ impl<E: Engine> NIFS<E> {
     U2: &R1CSInstance<E>,
     W2: &R1CSWitness<E>,
   ) -> Result<(NIFS<E>, (FoldedInstance<E>, FoldedWitness<E>)), NovaError> {
+    let step = Self::prepare(ck, ro_consts, pp_digest, S, U2, W2)?;
+    Self::finish(ck, S, U1, W1, U2, W2, step)
+  }
+
+  /// Runs `NIFS::prove`, but delegates the sum-fold step, which computes the sum-check
+  /// polynomial, to `sumfold_fn`, e.g., a hardware accelerator. The result only verifies
+  /// if `sumfold_fn` agrees with the software implementation.
+  #[allow(clippy::too_many_arguments)]
+  pub fn prove_with_external_sumfold(
+    ck: &CommitmentKey<E>,
+    ro_consts: &RO2Constants<E>,
+    pp_digest: &E::Scalar,
+    S: &Structure<E>,
+    U1: &FoldedInstance<E>,
+    W1: &FoldedWitness<E>,
+    U2: &R1CSInstance<E>,
+    W2: &R1CSWitness<E>,
+    sumfold_fn: impl Fn(SumFoldInputs<'_, E>) -> SumFoldOutputs<E>,
+  ) -> Result<(NIFS<E>, (FoldedInstance<E>, FoldedWitness<E>)), NovaError> {
+    let step = Self::prepare(ck, ro_consts, pp_digest, S, U2, W2)?;
+    Self::finish_with(ck, S, U1, W1, U2, W2, step, sumfold_fn)
+  }
+
+  /// Runs `NIFS::prove` under a deadline, which is checked before each of its two phases:
+  /// committing to the power polynomial along with computing `Az2`, `Bz2`, `Cz2`, and the sum-fold.
+  /// A phase that has started runs to completion, so the call may return after `deadline`.
+  ///
+  /// Returns `NovaError::TimeoutExceeded` if `deadline` has passed before a phase starts.
+  #[allow(clippy::too_many_arguments)]
+  pub fn prove_with_timeout(
+    ck: &CommitmentKey<E>,
+    ro_consts: &RO2Constants<E>,
+    pp_digest: &E::Scalar,
+    S: &Structure<E>,
+    U1: &FoldedInstance<E>,
+    W1: &FoldedWitness<E>,
+    U2: &R1CSInstance<E>,
+    W2: &R1CSWitness<E>,
+    deadline: Instant,
+  ) -> Result<(NIFS<E>, (FoldedInstance<E>, FoldedWitness<E>)), NovaError> {
+    let check_deadline = || {
+      if Instant::now() < deadline {
+        Ok(())
+      } else {
+        Err(NovaError::TimeoutExceeded)
+      }
+    };
+
+    check_deadline()?;
+    let step = Self::prepare(ck, ro_consts, pp_digest, S, U2, W2)?;
+    check_deadline()?;
+    Self::finish(ck, S, U1, W1, U2, W2, step)
+  }
+
+  /// Runs `NIFS::prove`, but first checks the running pair `(U1, W1)` when `reference` is provided,
+  /// e.g., the running instance recorded after the previous step of a long IVC chain.
+  /// The check requires `U1 == reference` and that `W1` satisfies `U1` under `S`, which catches
+  /// a running pair that was corrupted in between, e.g., by a hardware fault.
+  ///
+  /// Returns `NovaError::AccumulatorDrift` if either condition fails.
+  #[allow(clippy::too_many_arguments)]
+  pub fn prove_with_accumulation_check(
+    ck: &CommitmentKey<E>,
+    ro_consts: &RO2Constants<E>,
+    pp_digest: &E::Scalar,
+    S: &Structure<E>,
+    U1: &FoldedInstance<E>,
+    W1: &FoldedWitness<E>,
+    U2: &R1CSInstance<E>,
+    W2: &R1CSWitness<E>,
+    reference: Option<&FoldedInstance<E>>,
+  ) -> Result<(NIFS<E>, (FoldedInstance<E>, FoldedWitness<E>)), NovaError> {
+    if let Some(reference) = reference {
+      if U1 != reference {
+        return Err(NovaError::AccumulatorDrift {
+          reason: "U1 != reference".to_string(),
+        });
+      }
+      S.is_sat(ck, U1, W1)
+        .map_err(|e| NovaError::AccumulatorDrift {
+          reason: e.to_string(),
+        })?;
+    }
+
+    Self::prove(ck, ro_consts, pp_digest, S, U1, W1, U2, W2)
+  }
+
+  /// Runs `NIFS::prove` for an incoming witness that was committed in segments, e.g., in a
+  /// multi-round protocol where `w1` is committed before the challenges that `w2` depends on.
+  /// Segment `i` is committed under `segment_keys[i]`, and the segments make up `W2` in order.
+  ///
+  /// The segments are concatenated, with the sum of their blinds as the blind of `W2`, which requires
+  /// the segment commitments to add up to `U2.comm_W`; this holds when the segment keys are the
+  /// consecutive pieces of `ck`, e.g., obtained via `CommitmentKeyExtTrait::split_at`.
+  ///
+  /// Returns `NovaError::InvalidInputLength` if there is not one key per segment,
+  /// `NovaError::InvalidWitnessLength` if the segments do not add up to `num_vars` entries,
+  /// and `NovaError::UnSat` if the segment commitments do not add up to `U2.comm_W`.
+  #[allow(clippy::too_many_arguments)]
+  pub fn prove_with_witness_segments(
+    ck: &CommitmentKey<E>,
+    ro_consts: &RO2Constants<E>,
+    pp_digest: &E::Scalar,
+    S: &Structure<E>,
+    U1: &FoldedInstance<E>,
+    W1: &FoldedWitness<E>,
+    U2: &R1CSInstance<E>,
+    segments: &[R1CSWitness<E>],
+    segment_keys: &[CommitmentKey<E>],
+  ) -> Result<(NIFS<E>, (FoldedInstance<E>, FoldedWitness<E>)), NovaError> {
+    if segments.len() != segment_keys.len() {
+      return Err(NovaError::InvalidInputLength);
+    }
+    if segments.iter().map(|w| w.W.len()).sum::<usize>() != S.S.num_vars {
+      return Err(NovaError::InvalidWitnessLength);
+    }
+
+    let comm_W = segments
+      .par_iter()
+      .zip(segment_keys.par_iter())
+      .map(|(w, ck)| w.commit(ck))
+      .reduce(CE::<E>::commitment_to_zero, |acc, c| acc + c);
+    if comm_W != U2.comm_W {
+      return Err(NovaError::UnSat {
+        reason: "segment commitments do not add up to U2.comm_W".to_string(),
+      });
+    }
+
+    let W2 = R1CSWitness {
+      W: segments.iter().flat_map(|w| w.W.iter().copied()).collect(),
+      r_W: segments.iter().map(|w| w.r_W).sum(),
+    };
+    Self::prove(ck, ro_consts, pp_digest, S, U1, W1, U2, &W2)
+  }
+
+  /// The software implementation of the sum-fold step
+  fn sumfold(inputs: SumFoldInputs<'_, E>) -> SumFoldOutputs<E> {
+    let (e0, e2, e3, e4, e5) = Self::prove_helper(
+      &inputs.rho,
+      (inputs.left, inputs.right),
+      inputs.E1,
+      inputs.Az1,
+      inputs.Bz1,
+      inputs.Cz1,
+      inputs.E2,
+      inputs.Az2,
+      inputs.Bz2,
+      inputs.Cz2,
+    );
+    SumFoldOutputs {
+      evals: [e0, e2, e3, e4, e5],
+    }
+  }
+
+  /// Runs the part of `NIFS::prove` that depends only on `(U2, W2)`: the transcript up to `rho`,
+  /// the commitment to the power polynomial, and the products `Az2`, `Bz2`, and `Cz2`
+  fn prepare(
+    ck: &CommitmentKey<E>,
+    ro_consts: &RO2Constants<E>,
+    pp_digest: &E::Scalar,
+    S: &Structure<E>,
+    U2: &R1CSInstance<E>,
+    W2: &R1CSWitness<E>,
+  ) -> Result<PreparedStep<E>, NovaError> {
     // initialize a new RO
     let mut ro = E::RO2::new(ro_consts.clone());
 
@@ -221,14 +522,66 @@ impl<E: Engine> NIFS<E> {
 
     // compute a commitment to the eq polynomial
     let E = PowPolynomial::new(&tau, S.ell).split_evals(S.left, S.right);
+    debug_assert!(PowPolynomial::verify_split_evals(&tau, &E, S.left, S.right).is_ok());
     let r_E = E::Scalar::random(&mut OsRng);
-    let comm_E = CE::<E>::commit(ck, &E, &r_E);
+    let comm_E = CE::<E>::try_commit(ck, &E, &r_E)?;
 
     comm_E.absorb_in_ro2(&mut ro); // absorb the commitment in the NIFS
 
     // compute a challenge from the RO
     let rho = ro.squeeze(NUM_CHALLENGE_BITS);
 
+    let z2 = [W2.W.clone(), vec![E::Scalar::ONE], U2.X.clone()].concat();
+    let (Az2, Bz2, Cz2) = S.S.multiply_vec(&z2)?;
+
+    Ok(PreparedStep {
+      ro,
+      rho,
+      E,
+      r_E,
+      comm_E,
+      Az2,
+      Bz2,
+      Cz2,
+    })
+  }
+
+  /// Completes `NIFS::prove` from the output of `prepare` on `(U2, W2)`
+  fn finish(
+    ck: &CommitmentKey<E>,
+    S: &Structure<E>,
+    U1: &FoldedInstance<E>,
+    W1: &FoldedWitness<E>,
+    U2: &R1CSInstance<E>,
+    W2: &R1CSWitness<E>,
+    step: PreparedStep<E>,
+  ) -> Result<(NIFS<E>, (FoldedInstance<E>, FoldedWitness<E>)), NovaError> {
+    Self::finish_with(ck, S, U1, W1, U2, W2, step, Self::sumfold)
+  }
+
+  #[allow(clippy::too_many_arguments)]
+  #[cfg_attr(not(feature = "safe-mode"), allow(unused_variables))]
+  fn finish_with(
+    ck: &CommitmentKey<E>,
+    S: &Structure<E>,
+    U1: &FoldedInstance<E>,
+    W1: &FoldedWitness<E>,
+    U2: &R1CSInstance<E>,
+    W2: &R1CSWitness<E>,
+    step: PreparedStep<E>,
+    sumfold_fn: impl Fn(SumFoldInputs<'_, E>) -> SumFoldOutputs<E>,
+  ) -> Result<(NIFS<E>, (FoldedInstance<E>, FoldedWitness<E>)), NovaError> {
+    let PreparedStep {
+      mut ro,
+      rho,
+      E,
+      r_E,
+      comm_E,
+      Az2,
+      Bz2,
+      Cz2,
+    } = step;
+
     // We now run a single round of the sum-check protocol to establish
     // T = (1-rho) * T1 + rho * T2, where T1 comes from the running instance and T2 = 0
     let T = (E::Scalar::ONE - rho) * U1.T;
@@ -236,22 +589,22 @@ impl<E: Engine> NIFS<E> {
     let z1 = [W1.W.clone(), vec![U1.u], U1.X.clone()].concat();
     let (Az1, Bz1, Cz1) = S.S.multiply_vec(&z1)?;
 
-    let z2 = [W2.W.clone(), vec![E::Scalar::ONE], U2.X.clone()].concat();
-    let (Az2, Bz2, Cz2) = S.S.multiply_vec(&z2)?;
-
     // compute the sum-check polynomial's evaluations at 0, 2, 3
-    let (eval_point_0, eval_point_2, eval_point_3, eval_point_4, eval_point_5) = Self::prove_helper(
-      &rho,
-      (S.left, S.right),
-      &W1.E,
-      &Az1,
-      &Bz1,
-      &Cz1,
-      &E,
-      &Az2,
-      &Bz2,
-      &Cz2,
-    );
+    let SumFoldOutputs {
+      evals: [eval_point_0, eval_point_2, eval_point_3, eval_point_4, eval_point_5],
+    } = sumfold_fn(SumFoldInputs {
+      rho,
+      left: S.left,
+      right: S.right,
+      E1: &W1.E,
+      Az1: &Az1,
+      Bz1: &Bz1,
+      Cz1: &Cz1,
+      E2: &E,
+      Az2: &Az2,
+      Bz2: &Bz2,
+      Cz2: &Cz2,
+    });
 
     let evals = vec![
       eval_point_0,
@@ -276,23 +629,349 @@ impl<E: Engine> NIFS<E> {
     let U = U1.fold(U2, &comm_E, &r_b, &T_out)?;
     let W = W1.fold(W2, &E, &r_E, &r_b)?;
 
+    // catch a bad witness locally rather than after the proof has been sent
+    #[cfg(feature = "safe-mode")]
+    {
+      U.verify_against_witness(ck, &W)?;
+      U.T_accumulation_invariant(S, &W)?;
+      S.is_sat(ck, &U, &W)?;
+    }
+
     // return the folded instance and witness
     Ok((Self { comm_E, poly }, (U, W)))
   }
 
-  /// Takes as input a relaxed R1CS instance `U1` and R1CS instance `U2`
-  /// with the same shape and defined with respect to the same parameters,
-  /// and outputs a folded instance `U` with the same shape,
-  /// with the guarantee that the folded instance `U`
-  /// if and only if `U1` and `U2` are satisfiable.
-  #[cfg(test)]
-  pub fn verify(
-    &self,
+  /// Runs `NIFS::prove` on a batch of independent folding tasks in parallel.
+  ///
+  /// Each entry of `inputs` is a tuple `(U1, W1, U2, W2)` that is folded with its own RO,
+  /// and the results are returned in the same order as `inputs`.
+  pub fn prove_batch_parallel(
+    ck: &CommitmentKey<E>,
+    ro_consts: &RO2Constants<E>,
+    pp_digest: &E::Scalar,
+    S: &Structure<E>,
+    inputs: Vec<(
+      FoldedInstance<E>,
+      FoldedWitness<E>,
+      R1CSInstance<E>,
+      R1CSWitness<E>,
+    )>,
+  ) -> Vec<Result<(NIFS<E>, (FoldedInstance<E>, FoldedWitness<E>)), NovaError>> {
+    inputs
+      .par_iter()
+      .map(|(U1, W1, U2, W2)| Self::prove(ck, ro_consts, pp_digest, S, U1, W1, U2, W2))
+      .collect()
+  }
+
+  /// Folds a chain of R1CS instance-witness pairs `steps` into the running pair `(U, W)`,
+  /// one `NIFS::prove` per step, and returns a lazy iterator over the resulting NIFS messages
+  /// in the order of `steps`.
+  ///
+  /// Folding step `n` needs the running pair produced by step `n - 1`, but the part of `prove`
+  /// that only depends on the incoming pair (its transcript up to `rho`, the commitment to the
+  /// power polynomial, and its matrix-vector products) does not. While step `n` is folded,
+  /// that part of step `n + 1` runs alongside it with `rayon::join`.
+  ///
+  /// The iterator stops after the first error. The running pair after the last produced
+  /// message is returned by `IncrementalNIFS::into_running`.
+  pub fn prove_incremental<'a, I>(
+    ck: &'a CommitmentKey<E>,
+    ro_consts: &'a RO2Constants<E>,
+    pp_digest: &E::Scalar,
+    S: &'a Structure<E>,
+    U: FoldedInstance<E>,
+    W: FoldedWitness<E>,
+    steps: I,
+  ) -> IncrementalNIFS<'a, E, I::IntoIter>
+  where
+    I: IntoIterator<Item = (R1CSInstance<E>, R1CSWitness<E>)>,
+  {
+    IncrementalNIFS {
+      ck,
+      ro_consts,
+      pp_digest: *pp_digest,
+      S,
+      U,
+      W,
+      steps: steps.into_iter(),
+      next: None,
+      failed: false,
+    }
+  }
+
+  /// Runs `NIFS::prove` and writes the resulting NIFS to `sink` instead of returning it.
+  ///
+  /// Each component is written with `bincode` as soon as it is computed: `comm_E` right after
+  /// the power polynomial is committed, before the sum-fold runs, and then the sum-check
+  /// polynomial `poly`. The bytes are identical to `bincode::serialize` applied to the `NIFS`,
+  /// and `NIFS::read_streaming` reads them back in the same order.
+  /// Only the folded instance-witness pair is returned.
+  #[allow(clippy::too_many_arguments)]
+  pub fn prove_streaming<W: Write>(
+    ck: &CommitmentKey<E>,
     ro_consts: &RO2Constants<E>,
     pp_digest: &E::Scalar,
+    S: &Structure<E>,
     U1: &FoldedInstance<E>,
+    W1: &FoldedWitness<E>,
     U2: &R1CSInstance<E>,
-  ) -> Result<FoldedInstance<E>, NovaError> {
+    W2: &R1CSWitness<E>,
+    sink: &mut W,
+  ) -> Result<(FoldedInstance<E>, FoldedWitness<E>), NovaError> {
+    let to_err = |e: bincode::Error| NovaError::SerializationError {
+      reason: e.to_string(),
+    };
+
+    let step = Self::prepare(ck, ro_consts, pp_digest, S, U2, W2)?;
+    bincode::serialize_into(&mut *sink, &step.comm_E).map_err(to_err)?;
+
+    let (nifs, (U, W)) = Self::finish(ck, S, U1, W1, U2, W2, step)?;
+    bincode::serialize_into(&mut *sink, &nifs.poly).map_err(to_err)?;
+
+    Ok((U, W))
+  }
+
+  /// Reads a NIFS written by `prove_streaming` from `source`, one component at a time
+  /// in the order they were written: `comm_E`, then the sum-check polynomial `poly`.
+  ///
+  /// Returns `NovaError::SerializationError` if `source` ends early or holds malformed bytes.
+  pub fn read_streaming<R: Read>(source: &mut R) -> Result<Self, NovaError> {
+    let to_err = |e: bincode::Error| NovaError::SerializationError {
+      reason: e.to_string(),
+    };
+
+    let comm_E = bincode::deserialize_from(&mut *source).map_err(to_err)?;
+    let poly = bincode::deserialize_from(&mut *source).map_err(to_err)?;
+
+    Ok(Self { comm_E, poly })
+  }
+
+  /// Encodes the NIFS as Ethereum ABI calldata for the static tuple
+  /// `(uint256 x, uint256 y, uint256[6] coeffs)`: the affine coordinates of `comm_E`
+  /// followed by the coefficients of the degree-5 sum-check polynomial, starting with the constant term.
+  /// Every word is a 32-byte big-endian integer, and the point at infinity is encoded as `(0, 0)`.
+  pub fn serialize_for_evm(&self) -> Vec<u8> {
+    let (x, y, _is_infinity) = self.comm_E.to_coordinates();
+    [to_evm_word(&x), to_evm_word(&y)]
+      .into_iter()
+      .chain(self.poly.coeffs.iter().map(to_evm_word))
+      .flatten()
+      .collect()
+  }
+
+  /// Decodes a NIFS from the calldata produced by `serialize_for_evm`
+  pub fn deserialize_from_evm(bytes: &[u8]) -> Result<Self, NovaError> {
+    // two coordinates followed by the coefficients of a degree-5 polynomial
+    if bytes.len() != 32 * (2 + 6) {
+      return Err(NovaError::InvalidInputLength);
+    }
+    let mut words = bytes.chunks_exact(32);
+
+    let x: E::Base = from_evm_word(words.next().unwrap())?;
+    let y: E::Base = from_evm_word(words.next().unwrap())?;
+    let is_infinity = x == E::Base::ZERO && y == E::Base::ZERO;
+    let comm_E = Commitment::<E>::from_coordinates(x, y, is_infinity).ok_or(
+      NovaError::SerializationError {
+        reason: "comm_E is not a point on the curve".to_string(),
+      },
+    )?;
+
+    let coeffs = words
+      .map(from_evm_word::<E::Scalar>)
+      .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Self {
+      comm_E,
+      poly: UniPoly { coeffs },
+    })
+  }
+
+  /// Flattens the NIFS into scalars in the order in which `AllocatedNIFS::alloc` allocates them,
+  /// e.g., to feed it as a witness to an outer circuit: `comm_E` as the `BN_N_LIMBS` limbs of its
+  /// x-coordinate, the limbs of its y-coordinate, and its infinity flag (the same encoding under
+  /// which it is absorbed into the RO), followed by the coefficients of the sum-check polynomial.
+  pub fn to_field_elements(&self) -> Vec<E::Scalar> {
+    let (x, y, is_infinity) = self.comm_E.to_coordinates();
+    to_bignat_repr(&x)
+      .into_iter()
+      .chain(to_bignat_repr(&y))
+      .chain([E::Scalar::from(is_infinity as u64)])
+      .chain(self.poly.coeffs.iter().copied())
+      .collect()
+  }
+
+  /// Returns the number of scalars `to_field_elements` produces for a sum-check polynomial of the given `degree`
+  pub const fn field_element_count(degree: usize) -> usize {
+    2 * BN_N_LIMBS + degree + 2
+  }
+
+  /// Reconstructs a NIFS whose sum-check polynomial has the given `degree` from the output of
+  /// `to_field_elements`.
+  ///
+  /// Returns `NovaError::InvalidInputLength` if `elems` does not have `field_element_count(degree)`
+  /// entries, and `NovaError::SerializationError` if a limb is out of range, the infinity flag is
+  /// not a bit, or `comm_E` is not a point on the curve.
+  pub fn from_field_elements(elems: &[E::Scalar], degree: usize) -> Result<Self, NovaError> {
+    if elems.len() != Self::field_element_count(degree) {
+      return Err(NovaError::InvalidInputLength);
+    }
+    let err = |reason: &str| NovaError::SerializationError {
+      reason: reason.to_string(),
+    };
+
+    let (limbs, rest) = elems.split_at(2 * BN_N_LIMBS);
+    let coord = |limbs: &[E::Scalar]| {
+      if limbs
+        .iter()
+        .any(|l| f_to_nat(l).bits() > BN_LIMB_WIDTH as u64)
+      {
+        return Err(err("limb is out of range"));
+      }
+      nat_to_f::<E::Base>(&limbs_to_nat::<E::Scalar, _, _>(
+        limbs.iter(),
+        BN_LIMB_WIDTH,
+      ))
+      .ok_or_else(|| err("coordinate is not in the base field"))
+    };
+    let (x, y) = (coord(&limbs[..BN_N_LIMBS])?, coord(&limbs[BN_N_LIMBS..])?);
+
+    let is_infinity = match rest[0] {
+      f if f == E::Scalar::ZERO => false,
+      f if f == E::Scalar::ONE => true,
+      _ => return Err(err("infinity flag is not a bit")),
+    };
+    let comm_E = Commitment::<E>::from_coordinates(x, y, is_infinity)
+      .ok_or_else(|| err("comm_E is not a point on the curve"))?;
+
+    Ok(Self {
+      comm_E,
+      poly: UniPoly {
+        coeffs: rest[1..].to_vec(),
+      },
+    })
+  }
+
+  /// Returns a SHA-256 hash of the running instance `U` and `step_count`, as a lightweight
+  /// checkpoint of the prover's progress: recording it periodically and comparing it against a
+  /// reference run detects a diverging or corrupted running instance without storing the witness.
+  ///
+  /// `U` is hashed through its fixed-width little-endian `bincode` encoding, which covers every
+  /// scalar and commitment in it, followed by `step_count` in little-endian.
+  pub fn prover_state_hash(U: &FoldedInstance<E>, step_count: u64) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    bincode::DefaultOptions::new()
+      .with_little_endian()
+      .with_fixint_encoding()
+      .serialize_into(&mut hasher, U)
+      .expect("serializing a FoldedInstance into a hasher cannot fail");
+    hasher.update(step_count.to_le_bytes());
+    hasher.finalize().into()
+  }
+
+  /// Returns the number of scalars and commitments in the NIFS:
+  /// the coefficients of the sum-check polynomial and `comm_E`
+  pub fn size_in_field_elements(&self) -> NIFSSize {
+    NIFSSize {
+      num_scalars: self.poly.coeffs.len(),
+      num_commitments: 1,
+    }
+  }
+
+  /// Returns the size of the NIFS in bytes, counting each scalar by its canonical encoding
+  /// and each commitment by the canonical encodings of its two affine coordinates
+  pub fn size_in_bytes(&self) -> usize {
+    let size = self.size_in_field_elements();
+    let scalar_bytes = <E::Scalar as PrimeField>::Repr::default().as_ref().len();
+    let base_bytes = <E::Base as PrimeField>::Repr::default().as_ref().len();
+    size.num_scalars * scalar_bytes + size.num_commitments * 2 * base_bytes
+  }
+
+  /// Returns the number of R1CS constraints that the augmented circuit spends to verify one `NIFS<E>`.
+  ///
+  /// The count covers allocating the proof (`comm_E` and the degree-5 sum-check polynomial),
+  /// the untrusted hints `comm_W_fold` and `comm_E_fold`, and `AllocatedNIFS::verify`:
+  /// the RO absorbs and the squeezes of `tau`, `rho`, and `r_b`, the check `poly(0) + poly(1) = T`,
+  /// the evaluation of `eq(rho, r_b)` and `poly(r_b)`, and the fold of `FoldedInstance`
+  /// with its nonnative group operations. Allocating `U1`, `U2`, and `pp_digest` is not included,
+  /// since the augmented circuit needs those regardless of the folding scheme.
+  ///
+  /// The count does not depend on the size of the `Structure`: the NIFS runs a single sum-check
+  /// round over the two instances being folded, and the power-check instance for `tau` is not
+  /// verified in-circuit. It is obtained by synthesizing the verifier circuit, so it is exact.
+  pub fn num_verifier_constraints() -> usize {
+    let mut cs: ShapeCS<E> = ShapeCS::new();
+    Self::synthesize_verifier(&mut cs)
+      .expect("synthesizing the verifier circuit in a ShapeCS does not fail")
+  }
+
+  /// Synthesizes the in-circuit verifier into `cs` and returns the number of constraints it added
+  fn synthesize_verifier(cs: &mut ShapeCS<E>) -> Result<usize, SynthesisError> {
+    let pp_digest = AllocatedNum::alloc(cs.namespace(|| "pp_digest"), || Ok(E::Scalar::ZERO))?;
+    let U1 = AllocatedFoldedInstance::alloc(cs.namespace(|| "U1"), None)?;
+    let U2 = AllocatedNonnativeR1CSInstance::alloc(cs.namespace(|| "U2"), None)?;
+    let num_constraints_before = cs.num_constraints();
+
+    let nifs = AllocatedNIFS::<E>::alloc(cs.namespace(|| "nifs"), None, 5)?;
+    let comm_W_fold = AllocatedNonnativePoint::alloc(cs.namespace(|| "comm_W_fold"), None)?;
+    let comm_E_fold = AllocatedNonnativePoint::alloc(cs.namespace(|| "comm_E_fold"), None)?;
+    nifs.verify(
+      cs.namespace(|| "verify"),
+      &pp_digest,
+      &U1,
+      &U2,
+      &comm_W_fold,
+      &comm_E_fold,
+      RO2ConstantsCircuit::<E>::default(),
+    )?;
+
+    Ok(cs.num_constraints() - num_constraints_before)
+  }
+
+  /// Returns the round messages of the sum-check that runs inside the NIFS
+  /// together with the verifier's challenges.
+  ///
+  /// The challenges are recomputed by replaying the transcript, so `pp_digest` and `U2`
+  /// must be the ones provided to `NIFS::prove`. Since the NIFS folds two instances,
+  /// the sum-check has a single round, whose challenge is `r_b`.
+  pub fn extract_round_messages(
+    &self,
+    ro_consts: &RO2Constants<E>,
+    pp_digest: &E::Scalar,
+    U2: &R1CSInstance<E>,
+  ) -> Vec<SumCheckRound<E>> {
+    let (_rho, r_b) = self.challenges(ro_consts, pp_digest, U2);
+    vec![SumCheckRound {
+      univariate_poly: self.poly.coeffs.clone(),
+      challenge: r_b,
+    }]
+  }
+
+  /// Replays the transcript of the NIFS and returns the challenges `rho` and `r_b`
+  fn challenges(
+    &self,
+    ro_consts: &RO2Constants<E>,
+    pp_digest: &E::Scalar,
+    U2: &R1CSInstance<E>,
+  ) -> (E::Scalar, E::Scalar) {
+    let (mut ro, rho) = Self::transcript_up_to_rho(ro_consts, pp_digest, U2, &self.comm_E);
+
+    // absorb poly in the RO
+    <UniPoly<E::Scalar> as AbsorbInRO2Trait<E>>::absorb_in_ro2(&self.poly, &mut ro);
+
+    // squeeze a challenge
+    let r_b = ro.squeeze(NUM_CHALLENGE_BITS);
+
+    (rho, r_b)
+  }
+
+  /// Replays the transcript of a NIFS with commitment `comm_E` up to the challenge `rho`,
+  /// and returns the RO together with `rho`
+  fn transcript_up_to_rho(
+    ro_consts: &RO2Constants<E>,
+    pp_digest: &E::Scalar,
+    U2: &R1CSInstance<E>,
+    comm_E: &Commitment<E>,
+  ) -> (E::RO2, E::Scalar) {
     // initialize a new RO
     let mut ro = E::RO2::new(ro_consts.clone());
 
@@ -305,11 +984,39 @@ impl<E: Engine> NIFS<E> {
     // generate a challenge for the eq polynomial
     let _tau = ro.squeeze(NUM_CHALLENGE_BITS);
 
-    self.comm_E.absorb_in_ro2(&mut ro); // absorb the commitment in the NIFS
+    comm_E.absorb_in_ro2(&mut ro); // absorb the commitment in the NIFS
 
     // compute a challenge from the RO
     let rho = ro.squeeze(NUM_CHALLENGE_BITS);
 
+    (ro, rho)
+  }
+
+  /// Compresses the NIFS by omitting the linear term of the sum-check polynomial,
+  /// which the verifier recovers from `poly(0) + poly(1) = T`
+  pub fn compress(&self) -> CompressedNIFS<E> {
+    CompressedNIFS {
+      comm_E: self.comm_E,
+      poly: self.poly.compress(),
+    }
+  }
+
+  /// Takes as input a relaxed R1CS instance `U1` and R1CS instance `U2`
+  /// with the same shape and defined with respect to the same parameters,
+  /// and outputs a folded instance `U` with the same shape,
+  /// with the guarantee that the folded instance `U`
+  /// if and only if `U1` and `U2` are satisfiable.
+  #[cfg(test)]
+  pub fn verify(
+    &self,
+    ro_consts: &RO2Constants<E>,
+    pp_digest: &E::Scalar,
+    U1: &FoldedInstance<E>,
+    U2: &R1CSInstance<E>,
+  ) -> Result<FoldedInstance<E>, NovaError> {
+    // replay the transcript to obtain the challenges
+    let (rho, r_b) = self.challenges(ro_consts, pp_digest, U2);
+
     // T = (1-rho) * T1 + rho * T2, where T1 comes from the running instance and T2 = 0
     let T = (E::Scalar::ONE - rho) * U1.T;
 
@@ -318,12 +1025,6 @@ impl<E: Engine> NIFS<E> {
       return Err(NovaError::InvalidSumcheckProof);
     }
 
-    // absorb poly in the RO
-    <UniPoly<E::Scalar> as AbsorbInRO2Trait<E>>::absorb_in_ro2(&self.poly, &mut ro);
-
-    // squeeze a challenge
-    let r_b = ro.squeeze(NUM_CHALLENGE_BITS);
-
     // compute the sum-check polynomial's evaluations at r_b
     let eq_rho_r_b = (E::Scalar::ONE - rho) * (E::Scalar::ONE - r_b) + rho * r_b;
     let T_out = self.poly.evaluate(&r_b) * eq_rho_r_b.invert().unwrap(); // TODO: remove unwrap
@@ -335,6 +1036,93 @@ impl<E: Engine> NIFS<E> {
   }
 }
 
+impl<E: Engine, I> IncrementalNIFS<'_, E, I> {
+  /// Returns the running instance-witness pair after the last NIFS produced by the iterator
+  pub fn into_running(self) -> (FoldedInstance<E>, FoldedWitness<E>) {
+    (self.U, self.W)
+  }
+}
+
+impl<E: Engine, I> Iterator for IncrementalNIFS<'_, E, I>
+where
+  I: Iterator<Item = (R1CSInstance<E>, R1CSWitness<E>)>,
+  E::RO2: Send,
+{
+  type Item = Result<NIFS<E>, NovaError>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    if self.failed {
+      return None;
+    }
+
+    let (ck, ro_consts, pp_digest, S) = (self.ck, self.ro_consts, &self.pp_digest, self.S);
+    let prepare = |(U2, W2): (R1CSInstance<E>, R1CSWitness<E>)| {
+      let step = NIFS::prepare(ck, ro_consts, pp_digest, S, &U2, &W2);
+      (U2, W2, step)
+    };
+
+    let (U2, W2, step) = match self.next.take() {
+      Some(next) => next,
+      None => prepare(self.steps.next()?),
+    };
+    let step = match step {
+      Ok(step) => step,
+      Err(e) => {
+        self.failed = true;
+        return Some(Err(e));
+      }
+    };
+
+    // fold the current step while preparing the next one
+    let following = self.steps.next();
+    let (res, next) = rayon::join(
+      || NIFS::finish(ck, S, &self.U, &self.W, &U2, &W2, step),
+      || following.map(prepare),
+    );
+    self.next = next;
+
+    match res {
+      Ok((nifs, (U, W))) => {
+        self.U = U;
+        self.W = W;
+        Some(Ok(nifs))
+      }
+      Err(e) => {
+        self.failed = true;
+        Some(Err(e))
+      }
+    }
+  }
+}
+
+/// Encodes a field element as a 32-byte big-endian EVM word
+fn to_evm_word<F: PrimeField>(f: &F) -> Vec<u8> {
+  let repr = f.to_repr();
+  let mut word = vec![0u8; 32 - repr.as_ref().len()];
+  word.extend(repr.as_ref().iter().rev());
+  word
+}
+
+/// Decodes a field element from a 32-byte big-endian EVM word, rejecting non-canonical encodings
+fn from_evm_word<F: PrimeField>(word: &[u8]) -> Result<F, NovaError> {
+  let mut repr = F::Repr::default();
+  let len = repr.as_ref().len();
+  let (padding, value) = word.split_at(32 - len);
+  if padding.iter().any(|b| *b != 0) {
+    return Err(NovaError::SerializationError {
+      reason: "EVM word exceeds the field size".to_string(),
+    });
+  }
+  repr
+    .as_mut()
+    .iter_mut()
+    .zip(value.iter().rev())
+    .for_each(|(r, b)| *r = *b);
+  Option::from(F::from_repr(repr)).ok_or(NovaError::SerializationError {
+    reason: "EVM word is not a canonical field element".to_string(),
+  })
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -353,7 +1141,9 @@ mod tests {
     spartan::{direct::DirectCircuit, snark::RelaxedR1CSSNARK},
     traits::{circuit::NonTrivialCircuit, snark::RelaxedR1CSSNARKTrait, Engine, RO2Constants},
   };
+  use expect_test::expect;
   use ff::Field;
+  use std::time::Duration;
 
   fn execute_sequence<E: Engine>(
     ck: &CommitmentKey<E>,
@@ -428,7 +1218,29 @@ mod tests {
 
   fn test_tiny_r1cs_bellpepper_with<E: Engine, S: RelaxedR1CSSNARKTrait<E>>() {
     let ro_consts = RO2Constants::<E>::default();
+    let (ck, shape, U1, W1, U2, W2) = tiny_r1cs_bellpepper::<E, S>();
+
+    // execute a sequence of folds
+    execute_sequence(
+      &ck,
+      &ro_consts,
+      &<E as Engine>::Scalar::ZERO,
+      &shape,
+      &U1,
+      &W1,
+      &U2,
+      &W2,
+    );
+  }
 
+  fn tiny_r1cs_bellpepper<E: Engine, S: RelaxedR1CSSNARKTrait<E>>() -> (
+    CommitmentKey<E>,
+    R1CSShape<E>,
+    R1CSInstance<E>,
+    R1CSWitness<E>,
+    R1CSInstance<E>,
+    R1CSWitness<E>,
+  ) {
     // generate a non-trivial circuit
     let num_cons: usize = 32;
 
@@ -474,23 +1286,662 @@ mod tests {
     let W1 = W1.pad(&shape);
     let W2 = W2.pad(&shape);
 
-    // execute a sequence of folds
-    execute_sequence(
+    (ck, shape, U1, W1, U2, W2)
+  }
+
+  #[test]
+  fn test_tiny_r1cs_bellpepper() {
+    test_tiny_r1cs_bellpepper_with::<PallasEngine, RelaxedR1CSSNARK<_, EvaluationEngine<_>>>();
+    test_tiny_r1cs_bellpepper_with::<Bn256EngineKZG, RelaxedR1CSSNARK<_, HyperKZGEE<_>>>();
+    test_tiny_r1cs_bellpepper_with::<Secp256k1Engine, RelaxedR1CSSNARK<_, EvaluationEngine<_>>>();
+  }
+
+  fn test_prove_batch_parallel_with<E: Engine, S: RelaxedR1CSSNARKTrait<E>>() {
+    let ro_consts = RO2Constants::<E>::default();
+    let pp_digest = <E as Engine>::Scalar::ZERO;
+    let (ck, shape, U1, W1, U2, W2) = tiny_r1cs_bellpepper::<E, S>();
+    let str = Structure::new(&shape);
+
+    let running_U = FoldedInstance::default(&str);
+    let running_W = FoldedWitness::default(&str);
+    let inputs = vec![
+      (running_U.clone(), running_W.clone(), U1.clone(), W1),
+      (running_U.clone(), running_W.clone(), U2.clone(), W2),
+    ];
+
+    let res = NIFS::prove_batch_parallel(&ck, &ro_consts, &pp_digest, &str, inputs);
+    assert_eq!(res.len(), 2);
+
+    // the outputs must be in the same order as the inputs
+    for (res, U) in res.into_iter().zip([U1, U2].iter()) {
+      let (nifs, (U_fold, W_fold)) = res.unwrap();
+      let U_verify = nifs.verify(&ro_consts, &pp_digest, &running_U, U).unwrap();
+      assert_eq!(U_verify, U_fold);
+      assert!(str.is_sat(&ck, &U_fold, &W_fold).is_ok());
+    }
+  }
+
+  #[test]
+  fn test_prove_batch_parallel() {
+    test_prove_batch_parallel_with::<PallasEngine, RelaxedR1CSSNARK<_, EvaluationEngine<_>>>();
+    test_prove_batch_parallel_with::<Bn256EngineKZG, RelaxedR1CSSNARK<_, HyperKZGEE<_>>>();
+  }
+
+  fn test_prove_incremental_with<E: Engine, S: RelaxedR1CSSNARKTrait<E>>()
+  where
+    E::RO2: Send,
+  {
+    let ro_consts = RO2Constants::<E>::default();
+    let pp_digest = <E as Engine>::Scalar::ZERO;
+    let (ck, shape, U1, W1, U2, W2) = tiny_r1cs_bellpepper::<E, S>();
+    let str = Structure::new(&shape);
+
+    let running_U = FoldedInstance::default(&str);
+    let running_W = FoldedWitness::default(&str);
+    let steps = vec![(U1.clone(), W1.clone()), (U2.clone(), W2), (U1.clone(), W1)];
+    let mut prover = NIFS::prove_incremental(
       &ck,
       &ro_consts,
-      &<E as Engine>::Scalar::ZERO,
-      &shape,
-      &U1,
-      &W1,
+      &pp_digest,
+      &str,
+      running_U.clone(),
+      running_W,
+      steps,
+    );
+    let nifs = prover.by_ref().collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(nifs.len(), 3);
+    let (U_fold, W_fold) = prover.into_running();
+
+    // the messages verify in the order of the steps
+    let U_verify = nifs
+      .iter()
+      .zip([&U1, &U2, &U1])
+      .fold(running_U, |U, (nifs, U2)| {
+        nifs.verify(&ro_consts, &pp_digest, &U, U2).unwrap()
+      });
+    assert_eq!(U_verify, U_fold);
+    assert!(str.is_sat(&ck, &U_fold, &W_fold).is_ok());
+  }
+
+  #[test]
+  fn test_prove_incremental() {
+    test_prove_incremental_with::<PallasEngine, RelaxedR1CSSNARK<_, EvaluationEngine<_>>>();
+    test_prove_incremental_with::<Bn256EngineKZG, RelaxedR1CSSNARK<_, HyperKZGEE<_>>>();
+  }
+
+  fn test_external_sumfold_with<E: Engine, S: RelaxedR1CSSNARKTrait<E>>() {
+    let ro_consts = RO2Constants::<E>::default();
+    let pp_digest = <E as Engine>::Scalar::ZERO;
+    let (ck, shape, U1, W1, U2, W2) = tiny_r1cs_bellpepper::<E, S>();
+    let str = Structure::new(&shape);
+
+    let running_U = FoldedInstance::default(&str);
+    let running_W = FoldedWitness::default(&str);
+    let (_nifs, (running_U, running_W)) = NIFS::prove(
+      &ck, &ro_consts, &pp_digest, &str, &running_U, &running_W, &U1, &W1,
+    )
+    .unwrap();
+
+    // an external sum-fold that matches the software implementation yields a valid fold
+    let calls = std::sync::atomic::AtomicUsize::new(0);
+    let (nifs, (U_fold, W_fold)) = NIFS::prove_with_external_sumfold(
+      &ck,
+      &ro_consts,
+      &pp_digest,
+      &str,
+      &running_U,
+      &running_W,
       &U2,
       &W2,
+      |inputs| {
+        calls.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        NIFS::sumfold(inputs)
+      },
+    )
+    .unwrap();
+    assert_eq!(calls.into_inner(), 1);
+    let U_verify = nifs
+      .verify(&ro_consts, &pp_digest, &running_U, &U2)
+      .unwrap();
+    assert_eq!(U_verify, U_fold);
+    assert!(str.is_sat(&ck, &U_fold, &W_fold).is_ok());
+  }
+
+  #[test]
+  fn test_prove_with_external_sumfold() {
+    test_external_sumfold_with::<PallasEngine, RelaxedR1CSSNARK<_, EvaluationEngine<_>>>();
+    test_external_sumfold_with::<Bn256EngineKZG, RelaxedR1CSSNARK<_, HyperKZGEE<_>>>();
+  }
+
+  #[test]
+  fn test_prove_with_timeout() {
+    type E = PallasEngine;
+    let ro_consts = RO2Constants::<E>::default();
+    let pp_digest = <E as Engine>::Scalar::ZERO;
+    let (ck, shape, U1, W1, _U2, _W2) =
+      tiny_r1cs_bellpepper::<E, RelaxedR1CSSNARK<E, EvaluationEngine<E>>>();
+    let str = Structure::new(&shape);
+    let running_U = FoldedInstance::default(&str);
+    let running_W = FoldedWitness::default(&str);
+
+    let prove = |deadline| {
+      NIFS::prove_with_timeout(
+        &ck, &ro_consts, &pp_digest, &str, &running_U, &running_W, &U1, &W1, deadline,
+      )
+    };
+
+    let (nifs, (U_fold, _W_fold)) = prove(Instant::now() + Duration::from_secs(3600)).unwrap();
+    assert_eq!(
+      nifs.verify(&ro_consts, &pp_digest, &running_U, &U1),
+      Ok(U_fold)
     );
+    assert!(matches!(
+      prove(Instant::now()),
+      Err(NovaError::TimeoutExceeded)
+    ));
+  }
+
+  fn test_accumulation_check_with<E: Engine, S: RelaxedR1CSSNARKTrait<E>>() {
+    let ro_consts = RO2Constants::<E>::default();
+    let pp_digest = <E as Engine>::Scalar::ZERO;
+    let (ck, shape, U1, W1, U2, W2) = tiny_r1cs_bellpepper::<E, S>();
+    let str = Structure::new(&shape);
+
+    let running_U = FoldedInstance::default(&str);
+    let running_W = FoldedWitness::default(&str);
+    let (_nifs, (running_U, running_W)) = NIFS::prove_with_accumulation_check(
+      &ck, &ro_consts, &pp_digest, &str, &running_U, &running_W, &U1, &W1, None,
+    )
+    .unwrap();
+
+    // the recorded running instance is accepted
+    let reference = running_U.clone();
+    let (nifs, (U_fold, _W_fold)) = NIFS::prove_with_accumulation_check(
+      &ck,
+      &ro_consts,
+      &pp_digest,
+      &str,
+      &running_U,
+      &running_W,
+      &U2,
+      &W2,
+      Some(&reference),
+    )
+    .unwrap();
+    assert_eq!(
+      nifs.verify(&ro_consts, &pp_digest, &running_U, &U2),
+      Ok(U_fold)
+    );
+
+    // a drifted instance or witness is caught before folding
+    let mut U_drift = running_U.clone();
+    U_drift.T += <E as Engine>::Scalar::ONE;
+    let mut W_drift = running_W.clone();
+    W_drift.W[0] += <E as Engine>::Scalar::ONE;
+    for (U, W) in [(&U_drift, &running_W), (&running_U, &W_drift)] {
+      assert!(matches!(
+        NIFS::prove_with_accumulation_check(
+          &ck,
+          &ro_consts,
+          &pp_digest,
+          &str,
+          U,
+          W,
+          &U2,
+          &W2,
+          Some(&reference)
+        ),
+        Err(NovaError::AccumulatorDrift { .. })
+      ));
+    }
   }
 
   #[test]
-  fn test_tiny_r1cs_bellpepper() {
-    test_tiny_r1cs_bellpepper_with::<PallasEngine, RelaxedR1CSSNARK<_, EvaluationEngine<_>>>();
-    test_tiny_r1cs_bellpepper_with::<Bn256EngineKZG, RelaxedR1CSSNARK<_, HyperKZGEE<_>>>();
-    test_tiny_r1cs_bellpepper_with::<Secp256k1Engine, RelaxedR1CSSNARK<_, EvaluationEngine<_>>>();
+  fn test_prove_with_accumulation_check() {
+    test_accumulation_check_with::<PallasEngine, RelaxedR1CSSNARK<_, EvaluationEngine<_>>>();
+    test_accumulation_check_with::<Bn256EngineKZG, RelaxedR1CSSNARK<_, HyperKZGEE<_>>>();
+  }
+
+  #[test]
+  fn test_prove_with_witness_segments() {
+    use crate::provider::pedersen::CommitmentKeyExtTrait;
+    type E = PallasEngine;
+    type F = <E as Engine>::Scalar;
+
+    let ro_consts = RO2Constants::<E>::default();
+    let pp_digest = F::ZERO;
+    let (ck, shape, _U1, _W1, U2, W2) =
+      tiny_r1cs_bellpepper::<E, RelaxedR1CSSNARK<E, EvaluationEngine<E>>>();
+    let str = Structure::new(&shape);
+    let running_U = FoldedInstance::default(&str);
+    let running_W = FoldedWitness::default(&str);
+
+    // split the witness in two, with blinds that add up to the original one
+    let n = W2.W.len() / 2;
+    let r = F::random(&mut OsRng);
+    let segments = [
+      R1CSWitness::<E> {
+        W: W2.W[..n].to_vec(),
+        r_W: r,
+      },
+      R1CSWitness::<E> {
+        W: W2.W[n..].to_vec(),
+        r_W: W2.r_W - r,
+      },
+    ];
+    let (ck1, ck2) = ck.split_at(n).unwrap();
+    let keys = [ck1, ck2];
+
+    let (nifs, (U_fold, W_fold)) = NIFS::prove_with_witness_segments(
+      &ck, &ro_consts, &pp_digest, &str, &running_U, &running_W, &U2, &segments, &keys,
+    )
+    .unwrap();
+    assert_eq!(
+      nifs.verify(&ro_consts, &pp_digest, &running_U, &U2),
+      Ok(U_fold.clone())
+    );
+    assert!(str.is_sat(&ck, &U_fold, &W_fold).is_ok());
+
+    // the keys must match the segments they commit to
+    let swapped = [keys[1].clone(), keys[0].clone()];
+    assert!(matches!(
+      NIFS::prove_with_witness_segments(
+        &ck, &ro_consts, &pp_digest, &str, &running_U, &running_W, &U2, &segments, &swapped,
+      ),
+      Err(NovaError::UnSat { .. })
+    ));
+    assert!(matches!(
+      NIFS::prove_with_witness_segments(
+        &ck,
+        &ro_consts,
+        &pp_digest,
+        &str,
+        &running_U,
+        &running_W,
+        &U2,
+        &segments[..1],
+        &keys[..1],
+      ),
+      Err(NovaError::InvalidWitnessLength)
+    ));
+    assert!(matches!(
+      NIFS::prove_with_witness_segments(
+        &ck,
+        &ro_consts,
+        &pp_digest,
+        &str,
+        &running_U,
+        &running_W,
+        &U2,
+        &segments,
+        &keys[..1],
+      ),
+      Err(NovaError::InvalidInputLength)
+    ));
+  }
+
+  fn test_prover_state_hash_with<E: Engine, S: RelaxedR1CSSNARKTrait<E>>() {
+    let ro_consts = RO2Constants::<E>::default();
+    let pp_digest = <E as Engine>::Scalar::ZERO;
+    let (ck, shape, U1, W1, _U2, _W2) = tiny_r1cs_bellpepper::<E, S>();
+    let str = Structure::new(&shape);
+
+    let running_U = FoldedInstance::default(&str);
+    let running_W = FoldedWitness::default(&str);
+    let (_nifs, (U_fold, _W_fold)) = NIFS::prove(
+      &ck, &ro_consts, &pp_digest, &str, &running_U, &running_W, &U1, &W1,
+    )
+    .unwrap();
+
+    let hash = NIFS::prover_state_hash(&U_fold, 1);
+    assert_eq!(hash, NIFS::prover_state_hash(&U_fold.clone(), 1));
+
+    // external consumers can recompute the digest with plain SHA-256
+    let bytes = bincode::DefaultOptions::new()
+      .with_little_endian()
+      .with_fixint_encoding()
+      .serialize(&U_fold)
+      .unwrap();
+    let expected: [u8; 32] = Sha256::digest([bytes, 1u64.to_le_bytes().to_vec()].concat()).into();
+    assert_eq!(hash, expected);
+
+    assert_ne!(hash, NIFS::prover_state_hash(&U_fold, 2));
+    assert_ne!(hash, NIFS::prover_state_hash(&running_U, 1));
+  }
+
+  #[test]
+  fn test_prover_state_hash() {
+    test_prover_state_hash_with::<PallasEngine, RelaxedR1CSSNARK<_, EvaluationEngine<_>>>();
+    test_prover_state_hash_with::<Bn256EngineKZG, RelaxedR1CSSNARK<_, HyperKZGEE<_>>>();
+  }
+
+  fn test_compress_with<E: Engine, S: RelaxedR1CSSNARKTrait<E>>() {
+    let ro_consts = RO2Constants::<E>::default();
+    let pp_digest = <E as Engine>::Scalar::ZERO;
+    let (ck, shape, U1, W1, U2, W2) = tiny_r1cs_bellpepper::<E, S>();
+    let str = Structure::new(&shape);
+
+    // fold twice so that the running instance has a non-zero T
+    let running_U = FoldedInstance::default(&str);
+    let running_W = FoldedWitness::default(&str);
+    let (_nifs, (running_U, running_W)) = NIFS::prove(
+      &ck, &ro_consts, &pp_digest, &str, &running_U, &running_W, &U1, &W1,
+    )
+    .unwrap();
+    let (nifs, (U_fold, _W_fold)) = NIFS::prove(
+      &ck, &ro_consts, &pp_digest, &str, &running_U, &running_W, &U2, &W2,
+    )
+    .unwrap();
+
+    let compressed = nifs.compress();
+    let decompressed = compressed.decompress(&ro_consts, &pp_digest, &running_U, &U2);
+    assert_eq!(decompressed.comm_E, nifs.comm_E);
+    assert_eq!(decompressed.poly, nifs.poly);
+    assert_eq!(
+      decompressed
+        .verify(&ro_consts, &pp_digest, &running_U, &U2)
+        .unwrap(),
+      U_fold
+    );
+
+    // compression saves one scalar
+    let size = bincode::serialize(&nifs).unwrap().len();
+    let size_compressed = bincode::serialize(&compressed).unwrap().len();
+    assert_eq!(size - size_compressed, 32);
+  }
+
+  #[test]
+  fn test_compress() {
+    test_compress_with::<PallasEngine, RelaxedR1CSSNARK<_, EvaluationEngine<_>>>();
+    test_compress_with::<Bn256EngineKZG, RelaxedR1CSSNARK<_, HyperKZGEE<_>>>();
+  }
+
+  #[test]
+  fn test_num_verifier_constraints() {
+    expect!["3266"].assert_eq(&NIFS::<PallasEngine>::num_verifier_constraints().to_string());
+    expect!["3434"].assert_eq(&NIFS::<Bn256EngineKZG>::num_verifier_constraints().to_string());
+    expect!["3713"].assert_eq(&NIFS::<Secp256k1Engine>::num_verifier_constraints().to_string());
+  }
+
+  fn test_extract_round_messages_with<E: Engine, S: RelaxedR1CSSNARKTrait<E>>() {
+    let ro_consts = RO2Constants::<E>::default();
+    let pp_digest = <E as Engine>::Scalar::ZERO;
+    let (ck, shape, U1, W1, U2, W2) = tiny_r1cs_bellpepper::<E, S>();
+    let str = Structure::new(&shape);
+
+    // fold twice so that the running instance is non-trivial
+    let running_U = FoldedInstance::default(&str);
+    let running_W = FoldedWitness::default(&str);
+    let (_nifs, (running_U, running_W)) = NIFS::prove(
+      &ck, &ro_consts, &pp_digest, &str, &running_U, &running_W, &U1, &W1,
+    )
+    .unwrap();
+    let (nifs, (U_fold, _W_fold)) = NIFS::prove(
+      &ck, &ro_consts, &pp_digest, &str, &running_U, &running_W, &U2, &W2,
+    )
+    .unwrap();
+
+    let rounds = nifs.extract_round_messages(&ro_consts, &pp_digest, &U2);
+    assert_eq!(rounds.len(), 1);
+    assert_eq!(rounds[0].univariate_poly, nifs.poly.coeffs);
+
+    // replay the round messages with a fresh transcript
+    let mut ro = E::RO2::new(ro_consts.clone());
+    ro.absorb(pp_digest);
+    U2.absorb_in_ro2(&mut ro);
+    let _tau = ro.squeeze(NUM_CHALLENGE_BITS);
+    nifs.comm_E.absorb_in_ro2(&mut ro);
+    let rho = ro.squeeze(NUM_CHALLENGE_BITS);
+    let poly = UniPoly {
+      coeffs: rounds[0].univariate_poly.clone(),
+    };
+    <UniPoly<E::Scalar> as AbsorbInRO2Trait<E>>::absorb_in_ro2(&poly, &mut ro);
+    let r_b = ro.squeeze(NUM_CHALLENGE_BITS);
+    assert_eq!(rounds[0].challenge, r_b);
+
+    // the round messages must be consistent with the claims in the running and folded instances
+    assert_eq!(
+      poly.eval_at_zero() + poly.eval_at_one(),
+      (E::Scalar::ONE - rho) * running_U.T
+    );
+    let eq_rho_r_b = (E::Scalar::ONE - rho) * (E::Scalar::ONE - r_b) + rho * r_b;
+    assert_eq!(poly.evaluate(&r_b), U_fold.T * eq_rho_r_b);
+  }
+
+  #[test]
+  fn test_extract_round_messages() {
+    test_extract_round_messages_with::<PallasEngine, RelaxedR1CSSNARK<_, EvaluationEngine<_>>>();
+    test_extract_round_messages_with::<Bn256EngineKZG, RelaxedR1CSSNARK<_, HyperKZGEE<_>>>();
+  }
+
+  /// The setup of the NIFS tests: the key and structure of `tiny_r1cs_bellpepper`, its first
+  /// satisfying incoming pair, and the default running pair it is folded into
+  struct Fixture<E: Engine> {
+    ro_consts: RO2Constants<E>,
+    pp_digest: E::Scalar,
+    ck: CommitmentKey<E>,
+    str: Structure<E>,
+    running_U: FoldedInstance<E>,
+    running_W: FoldedWitness<E>,
+    U1: R1CSInstance<E>,
+    W1: R1CSWitness<E>,
+  }
+
+  impl<E: Engine> Fixture<E> {
+    fn new<S: RelaxedR1CSSNARKTrait<E>>() -> Self {
+      let (ck, shape, U1, W1, _U2, _W2) = tiny_r1cs_bellpepper::<E, S>();
+      let str = Structure::new(&shape);
+      Self {
+        ro_consts: RO2Constants::<E>::default(),
+        pp_digest: E::Scalar::ZERO,
+        ck,
+        running_U: FoldedInstance::default(&str),
+        running_W: FoldedWitness::default(&str),
+        str,
+        U1,
+        W1,
+      }
+    }
+
+    /// Verifies `nifs` for the incoming instance `U2` against the running instance `U`
+    fn verify(
+      &self,
+      nifs: &NIFS<E>,
+      U: &FoldedInstance<E>,
+      U2: &R1CSInstance<E>,
+    ) -> Result<FoldedInstance<E>, NovaError> {
+      nifs.verify(&self.ro_consts, &self.pp_digest, U, U2)
+    }
+  }
+
+  fn test_prove_streaming_with<E: Engine, S: RelaxedR1CSSNARKTrait<E>>() {
+    let f = Fixture::<E>::new::<S>();
+    let mut sink = Vec::new();
+    let (U_fold, W_fold) = NIFS::prove_streaming(
+      &f.ck,
+      &f.ro_consts,
+      &f.pp_digest,
+      &f.str,
+      &f.running_U,
+      &f.running_W,
+      &f.U1,
+      &f.W1,
+      &mut sink,
+    )
+    .unwrap();
+    assert!(f.str.is_sat(&f.ck, &U_fold, &W_fold).is_ok());
+
+    // the streamed bytes are read back component by component into a NIFS that verifies
+    let nifs = NIFS::<E>::read_streaming(&mut sink.as_slice()).unwrap();
+    assert_eq!(bincode::serialize(&nifs).unwrap(), sink);
+    assert_eq!(f.verify(&nifs, &f.running_U, &f.U1), Ok(U_fold));
+
+    // a truncated stream is an error
+    assert!(matches!(
+      NIFS::<E>::read_streaming(&mut &sink[..sink.len() - 1]),
+      Err(NovaError::SerializationError { .. })
+    ));
+  }
+
+  #[test]
+  fn test_prove_streaming() {
+    test_prove_streaming_with::<PallasEngine, RelaxedR1CSSNARK<_, EvaluationEngine<_>>>();
+    test_prove_streaming_with::<Bn256EngineKZG, RelaxedR1CSSNARK<_, HyperKZGEE<_>>>();
+  }
+
+  fn test_evm_serialization_with<E: Engine, S: RelaxedR1CSSNARKTrait<E>>() {
+    let ro_consts = RO2Constants::<E>::default();
+    let pp_digest = <E as Engine>::Scalar::ZERO;
+    let (ck, shape, U1, W1, _U2, _W2) = tiny_r1cs_bellpepper::<E, S>();
+    let str = Structure::new(&shape);
+
+    let running_U = FoldedInstance::default(&str);
+    let running_W = FoldedWitness::default(&str);
+    let (nifs, (U_fold, _W_fold)) = NIFS::prove(
+      &ck, &ro_consts, &pp_digest, &str, &running_U, &running_W, &U1, &W1,
+    )
+    .unwrap();
+
+    let bytes = nifs.serialize_for_evm();
+    assert_eq!(bytes.len(), 32 * 8);
+    assert_eq!(
+      nifs.size_in_field_elements(),
+      NIFSSize {
+        num_scalars: 6,
+        num_commitments: 1
+      }
+    );
+    assert_eq!(nifs.size_in_bytes(), bytes.len());
+
+    // the first word is the big-endian x-coordinate of comm_E
+    let (x, _, _) = nifs.comm_E.to_coordinates();
+    let mut x_le = bytes[..32].to_vec();
+    x_le.reverse();
+    assert_eq!(x_le, x.to_repr().as_ref());
+
+    let nifs_decoded = NIFS::<E>::deserialize_from_evm(&bytes).unwrap();
+    assert_eq!(nifs_decoded.serialize_for_evm(), bytes);
+    let U_verify = nifs_decoded
+      .verify(&ro_consts, &pp_digest, &running_U, &U1)
+      .unwrap();
+    assert_eq!(U_verify, U_fold);
+
+    // the identity commitment is encoded as (0, 0)
+    let nifs_zero = NIFS::<E> {
+      comm_E: E::CE::commitment_to_zero(),
+      poly: nifs.poly.clone(),
+    };
+    let bytes_zero = nifs_zero.serialize_for_evm();
+    assert!(bytes_zero[..64].iter().all(|b| *b == 0));
+    let nifs_zero_decoded = NIFS::<E>::deserialize_from_evm(&bytes_zero).unwrap();
+    assert_eq!(nifs_zero_decoded.comm_E, nifs_zero.comm_E);
+
+    // malformed inputs are rejected
+    assert_eq!(
+      NIFS::<E>::deserialize_from_evm(&bytes[1..]).err(),
+      Some(NovaError::InvalidInputLength)
+    );
+    let mut bad = bytes.clone();
+    bad[32..64].iter_mut().for_each(|b| *b = 0xff);
+    assert!(matches!(
+      NIFS::<E>::deserialize_from_evm(&bad),
+      Err(NovaError::SerializationError { .. })
+    ));
+    let mut bad = bytes;
+    bad[63] ^= 1;
+    assert!(matches!(
+      NIFS::<E>::deserialize_from_evm(&bad),
+      Err(NovaError::SerializationError { .. })
+    ));
+  }
+
+  fn test_field_elements_with<E: Engine, S: RelaxedR1CSSNARKTrait<E>>() {
+    let ro_consts = RO2Constants::<E>::default();
+    let pp_digest = <E as Engine>::Scalar::ZERO;
+    let (ck, shape, U1, W1, _U2, _W2) = tiny_r1cs_bellpepper::<E, S>();
+    let str = Structure::new(&shape);
+
+    let running_U = FoldedInstance::default(&str);
+    let running_W = FoldedWitness::default(&str);
+    let (nifs, (U_fold, _W_fold)) = NIFS::prove(
+      &ck, &ro_consts, &pp_digest, &str, &running_U, &running_W, &U1, &W1,
+    )
+    .unwrap();
+
+    // the layout is the one allocated by the verifier circuit
+    let elems = nifs.to_field_elements();
+    assert_eq!(elems.len(), NIFS::<E>::field_element_count(5));
+    let mut cs = SatisfyingAssignment::<E>::new();
+    AllocatedNIFS::alloc(cs.namespace(|| "nifs"), Some(&nifs), 5).unwrap();
+    assert_eq!(cs.aux_assignment(), &elems[..]);
+
+    let nifs_decoded = NIFS::<E>::from_field_elements(&elems, 5).unwrap();
+    assert_eq!(nifs_decoded.to_field_elements(), elems);
+    assert_eq!(
+      nifs_decoded.verify(&ro_consts, &pp_digest, &running_U, &U1),
+      Ok(U_fold)
+    );
+
+    // the identity commitment round-trips as well
+    let nifs_zero = NIFS::<E> {
+      comm_E: E::CE::commitment_to_zero(),
+      poly: nifs.poly.clone(),
+    };
+    let nifs_zero_decoded =
+      NIFS::<E>::from_field_elements(&nifs_zero.to_field_elements(), 5).unwrap();
+    assert_eq!(nifs_zero_decoded.comm_E, nifs_zero.comm_E);
+
+    // malformed inputs are rejected
+    assert_eq!(
+      NIFS::<E>::from_field_elements(&elems, 4).err(),
+      Some(NovaError::InvalidInputLength)
+    );
+    for i in [0, 2 * BN_N_LIMBS] {
+      let mut bad = elems.clone();
+      bad[i] = -<E as Engine>::Scalar::ONE;
+      assert!(matches!(
+        NIFS::<E>::from_field_elements(&bad, 5),
+        Err(NovaError::SerializationError { .. })
+      ));
+    }
+  }
+
+  #[test]
+  fn test_field_elements() {
+    test_field_elements_with::<PallasEngine, RelaxedR1CSSNARK<_, EvaluationEngine<_>>>();
+    test_field_elements_with::<Bn256EngineKZG, RelaxedR1CSSNARK<_, HyperKZGEE<_>>>();
+  }
+
+  #[test]
+  fn test_evm_serialization() {
+    test_evm_serialization_with::<PallasEngine, RelaxedR1CSSNARK<_, EvaluationEngine<_>>>();
+    test_evm_serialization_with::<Bn256EngineKZG, RelaxedR1CSSNARK<_, HyperKZGEE<_>>>();
+    test_evm_serialization_with::<Secp256k1Engine, RelaxedR1CSSNARK<_, EvaluationEngine<_>>>();
+  }
+
+  #[cfg(feature = "safe-mode")]
+  #[test]
+  fn test_prove_safe_mode() {
+    type E = PallasEngine;
+    let ro_consts = RO2Constants::<E>::default();
+    let pp_digest = <E as Engine>::Scalar::ZERO;
+    let (ck, shape, U1, W1, _U2, _W2) =
+      tiny_r1cs_bellpepper::<E, RelaxedR1CSSNARK<_, EvaluationEngine<_>>>();
+    let str = Structure::new(&shape);
+
+    let running_U = FoldedInstance::default(&str);
+    let running_W = FoldedWitness::default(&str);
+
+    // a witness that does not satisfy the instance is caught by the prover
+    let mut W_bad = W1.clone();
+    W_bad.W[0] += <E as Engine>::Scalar::ONE;
+    assert!(matches!(
+      NIFS::prove(&ck, &ro_consts, &pp_digest, &str, &running_U, &running_W, &U1, &W_bad),
+      Err(NovaError::UnSat { .. })
+    ));
+
+    assert!(
+      NIFS::prove(&ck, &ro_consts, &pp_digest, &str, &running_U, &running_W, &U1, &W1).is_ok()
+    );
   }
 }
@@ -1,12 +1,18 @@
 //! This module defines relations used in the Neutron folding scheme
 use crate::{
+  constants::BN_N_LIMBS,
   errors::NovaError,
   r1cs::{R1CSInstance, R1CSShape, R1CSWitness},
   spartan::math::Math,
   traits::{commitment::CommitmentEngineTrait, AbsorbInRO2Trait, Engine, ROTrait},
   Commitment, CommitmentKey,
 };
+#[cfg(feature = "json")]
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
 use ff::Field;
+#[cfg(feature = "json")]
+use ff::PrimeField;
+use rand_core::RngCore;
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 
@@ -36,6 +42,37 @@ pub struct FoldedWitness<E: Engine> {
   r_E: E::Scalar,
 }
 
+/// A Shamir share of a `FoldedWitness`, held by one party in a threshold proving setting
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct FoldedWitnessShare<E: Engine> {
+  /// index of the party holding the share, which is the evaluation point of the sharing polynomial
+  index: usize,
+  /// number of shares needed to reconstruct the witness
+  threshold: usize,
+  pub(crate) W: Vec<E::Scalar>,
+  r_W: E::Scalar,
+  pub(crate) E: Vec<E::Scalar>,
+  r_E: E::Scalar,
+}
+
+/// Intermediate values of a `FoldedWitness::fold_with_diagnostics` call, kept for debugging a
+/// fold that later fails to verify
+#[cfg(feature = "diagnostics")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FoldDiagnostics<E: Engine> {
+  /// Hamming norms, i.e., numbers of nonzero entries, of the running and the incoming `W` before the fold
+  pub hamming_norm_W: (usize, usize),
+  /// Hamming norms, i.e., numbers of nonzero entries, of the running and the incoming `E` before the fold
+  pub hamming_norm_E: (usize, usize),
+  /// the folding challenge
+  pub r_b: E::Scalar,
+  /// commitment to the folded `W`
+  pub comm_W: Commitment<E>,
+  /// commitment to the folded `E`
+  pub comm_E: Commitment<E>,
+}
+
 /// A type that holds instance information for a zero-fold relation
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(bound = "")]
@@ -56,7 +93,7 @@ impl<E: Engine> Structure<E> {
     let ell = S.num_cons.next_power_of_two().log_2();
 
     // we split ell into ell1 and ell2 such that ell1 + ell2 = ell and ell1 >= ell2
-    let ell1 = (ell + 1) / 2; // This ensures ell1 >= ell2
+    let ell1 = ell.div_ceil(2); // This ensures ell1 >= ell2
     let ell2 = ell / 2;
 
     Structure {
@@ -75,46 +112,10 @@ impl<E: Engine> Structure<E> {
     W: &FoldedWitness<E>,
   ) -> Result<(), NovaError> {
     // check if the witness is satisfying
-    let z = [W.W.clone(), vec![U.u], U.X.clone()].concat();
-    let (Az, Bz, Cz) = self.S.multiply_vec(&z)?;
-
-    // full_E is the outer outer product of E1 and E2
-    // E1 and E2 are splits of E
-    let (E1, E2) = W.E.split_at(self.left);
-    let mut full_E = vec![E::Scalar::ONE; self.left * self.right];
-    for i in 0..self.right {
-      for j in 0..self.left {
-        full_E[i * self.left + j] = E2[i] * E1[j];
-      }
-    }
-
-    let sum = full_E
-      .par_iter()
-      .zip(Az.par_iter())
-      .zip(Bz.par_iter())
-      .zip(Cz.par_iter())
-      .map(|(((e, a), b), c)| *e * ((*a) * (*b) - *c))
-      .reduce(|| E::Scalar::ZERO, |acc, x| acc + x);
-
-    if sum != U.T {
-      println!("sum: {:?}", sum);
-      println!("U.T: {:?}", U.T);
-      return Err(NovaError::UnSat {
-        reason: "sum != U.T".to_string(),
-      });
-    }
+    U.T_accumulation_invariant(self, W)?;
 
     // check the validity of the commitments
-    let comm_W = E::CE::commit(ck, &W.W, &W.r_W);
-    let comm_E = E::CE::commit(ck, &W.E, &W.r_E);
-
-    if comm_W != U.comm_W || comm_E != U.comm_E {
-      return Err(NovaError::UnSat {
-        reason: "comm_W != U.comm_W || comm_E != U.comm_E".to_string(),
-      });
-    }
-
-    Ok(())
+    U.verify_against_witness(ck, W)
   }
 }
 
@@ -129,6 +130,20 @@ impl<E: Engine> FoldedWitness<E> {
     }
   }
 
+  /// Returns the evaluations of the power polynomial held by the witness, in the split form
+  /// produced by `PowPolynomial::split_evals`, i.e., the left half followed by the right half
+  pub fn e_polynomial(&self) -> &[E::Scalar] {
+    &self.E
+  }
+
+  /// Returns a mutable reference to the evaluations returned by `e_polynomial`.
+  ///
+  /// Modifying them does not update `comm_E` in the corresponding `FoldedInstance`;
+  /// the caller must re-commit, or the witness no longer opens the instance
+  pub fn e_polynomial_mut(&mut self) -> &mut [E::Scalar] {
+    &mut self.E
+  }
+
   /// Fold the witness with another witness
   pub fn fold(
     &self,
@@ -137,39 +152,285 @@ impl<E: Engine> FoldedWitness<E> {
     r_E2: &E::Scalar,
     r_b: &E::Scalar,
   ) -> Result<Self, NovaError> {
-    // we need to compute the weighted sum using weights of (1-r_b) and r_b
-    let W = self
-      .W
-      .par_iter()
-      .zip(W2.W.par_iter())
-      .map(|(w1, w2)| *w1 + *r_b * (*w2 - *w1))
-      .collect::<Vec<_>>();
-    let r_W = (E::Scalar::ONE - r_b) * self.r_W + *r_b * W2.r_W;
+    // we need to compute the weighted sum using weights of (1-r_b) and r_b,
+    // i.e., W1 + r_b * (W2 - W1), which combines the blinds r_W the same way
+    let W1 = R1CSWitness {
+      W: self.W.clone(),
+      r_W: self.r_W,
+    };
+    let W2_minus_W1 = R1CSWitness::linear_combination(W2, &W1, &-E::Scalar::ONE)?;
+    let R1CSWitness { W, r_W } = R1CSWitness::linear_combination(&W1, &W2_minus_W1, r_b)?;
 
+    if self.E.len() != E2.len() {
+      return Err(NovaError::InvalidInputLength);
+    }
     let E = self
       .E
       .par_iter()
       .zip(E2.par_iter())
       .map(|(e1, e2)| *e1 + *r_b * (*e2 - *e1))
       .collect::<Vec<_>>();
-    let r_E = (E::Scalar::ONE - r_b) * self.r_E + *r_b * r_E2;
+
+    let (_, r_E) = self.reduce_blinding(W2, r_E2, r_b);
 
     Ok(Self { W, r_W, E, r_E })
   }
+
+  /// Computes the blinding factors `(r_W, r_E)` of the witness obtained by folding `W2`
+  /// (with `E2` committed under `r_E2`) into `self`, using the same weights `(1-r_b)` and `r_b` as `fold`
+  pub fn reduce_blinding(
+    &self,
+    W2: &R1CSWitness<E>,
+    r_E2: &E::Scalar,
+    r_b: &E::Scalar,
+  ) -> (E::Scalar, E::Scalar) {
+    let r_W = (E::Scalar::ONE - r_b) * self.r_W + *r_b * W2.r_W;
+    let r_E = (E::Scalar::ONE - r_b) * self.r_E + *r_b * r_E2;
+    (r_W, r_E)
+  }
+
+  /// Splits the witness into Shamir shares for `n_parties` parties such that any `threshold`
+  /// of them can reconstruct it, sharing every scalar of the witness with a polynomial of
+  /// degree `threshold - 1`. The share of the `i`-th party is the evaluation at `i`, for `i` in `1..=n_parties`.
+  ///
+  /// Returns `NovaError::InvalidInputLength` unless `1 <= threshold <= n_parties`.
+  pub fn split_into_parties(
+    &self,
+    n_parties: usize,
+    threshold: usize,
+    rng: &mut impl RngCore,
+  ) -> Result<Vec<FoldedWitnessShare<E>>, NovaError> {
+    if threshold == 0 || threshold > n_parties {
+      return Err(NovaError::InvalidInputLength);
+    }
+
+    let mut random_vec =
+      |len: usize| -> Vec<E::Scalar> { (0..len).map(|_| E::Scalar::random(&mut *rng)).collect() };
+
+    // coefficients of the sharing polynomial, where the constant term is the witness itself
+    let coeffs = std::iter::once(self.clone())
+      .chain((1..threshold).map(|_| {
+        let W = random_vec(self.W.len());
+        let r_W = random_vec(1)[0];
+        let E = random_vec(self.E.len());
+        let r_E = random_vec(1)[0];
+        FoldedWitness { W, r_W, E, r_E }
+      }))
+      .collect::<Vec<_>>();
+
+    Ok(
+      (1..=n_parties)
+        .map(|index| {
+          // evaluate the sharing polynomial at x = index using Horner's rule
+          let x = E::Scalar::from(index as u64);
+          let share = coeffs[..threshold - 1]
+            .iter()
+            .rev()
+            .fold(coeffs[threshold - 1].clone(), |acc, c| {
+              acc.scale_and_add(&x, c)
+            });
+
+          FoldedWitnessShare {
+            index,
+            threshold,
+            W: share.W,
+            r_W: share.r_W,
+            E: share.E,
+            r_E: share.r_E,
+          }
+        })
+        .collect(),
+    )
+  }
+
+  /// Reconstructs the witness from at least `threshold` shares produced by `split_into_parties`,
+  /// using Lagrange interpolation at zero over the first `threshold` shares
+  pub fn reconstruct_from_shares(shares: &[FoldedWitnessShare<E>]) -> Result<Self, NovaError> {
+    let first = shares.first().ok_or(NovaError::InvalidInputLength)?;
+    let threshold = first.threshold;
+    if shares.len() < threshold
+      || shares.iter().any(|s| {
+        s.threshold != threshold || s.W.len() != first.W.len() || s.E.len() != first.E.len()
+      })
+    {
+      return Err(NovaError::InvalidInputLength);
+    }
+
+    let shares = &shares[..threshold];
+    let xs = shares
+      .iter()
+      .map(|s| E::Scalar::from(s.index as u64))
+      .collect::<Vec<_>>();
+
+    // Lagrange coefficient for x_i at zero: prod_{j != i} x_j / (x_j - x_i)
+    let lambdas = (0..threshold)
+      .map(|i| {
+        let (num, den) = (0..threshold)
+          .filter(|&j| j != i)
+          .fold((E::Scalar::ONE, E::Scalar::ONE), |(num, den), j| {
+            (num * xs[j], den * (xs[j] - xs[i]))
+          });
+        Option::<E::Scalar>::from(den.invert())
+          .map(|den_inv| num * den_inv)
+          .ok_or(NovaError::InvalidIndex)
+      })
+      .collect::<Result<Vec<_>, _>>()?;
+
+    let combine = |f: &dyn Fn(&FoldedWitnessShare<E>) -> E::Scalar| -> E::Scalar {
+      shares
+        .iter()
+        .zip(lambdas.iter())
+        .map(|(s, l)| *l * f(s))
+        .sum()
+    };
+
+    let W = (0..first.W.len())
+      .into_par_iter()
+      .map(|k| combine(&|s| s.W[k]))
+      .collect::<Vec<_>>();
+    let E = (0..first.E.len())
+      .into_par_iter()
+      .map(|k| combine(&|s| s.E[k]))
+      .collect::<Vec<_>>();
+
+    Ok(FoldedWitness {
+      W,
+      r_W: combine(&|s| s.r_W),
+      E,
+      r_E: combine(&|s| s.r_E),
+    })
+  }
+
+  /// Computes `self * x + other` entry-wise
+  fn scale_and_add(&self, x: &E::Scalar, other: &Self) -> Self {
+    let W = self
+      .W
+      .par_iter()
+      .zip(other.W.par_iter())
+      .map(|(a, b)| *a * x + b)
+      .collect::<Vec<_>>();
+    let E = self
+      .E
+      .par_iter()
+      .zip(other.E.par_iter())
+      .map(|(a, b)| *a * x + b)
+      .collect::<Vec<_>>();
+
+    FoldedWitness {
+      W,
+      r_W: self.r_W * x + other.r_W,
+      E,
+      r_E: self.r_E * x + other.r_E,
+    }
+  }
 }
 
 impl<E: Engine> FoldedInstance<E> {
   /// Create a default instance
   pub fn default(S: &Structure<E>) -> Self {
     FoldedInstance {
-      comm_W: Commitment::<E>::default(),
-      comm_E: Commitment::<E>::default(),
+      comm_W: E::CE::commitment_to_zero(),
+      comm_E: E::CE::commitment_to_zero(),
       T: E::Scalar::ZERO,
       u: E::Scalar::ZERO,
       X: vec![E::Scalar::ZERO; S.S.num_io],
     }
   }
 
+  /// Initializes a new `FoldedInstance` from an `R1CSInstance` and a commitment `comm_E`
+  /// to the evaluations of its power polynomial.
+  ///
+  /// `T` starts at zero, since a satisfying R1CS witness makes every constraint vanish, and it
+  /// accumulates the claimed sums as instances are folded in.
+  pub fn from_r1cs_instance(U: &R1CSInstance<E>, comm_E: &Commitment<E>) -> Self {
+    FoldedInstance {
+      comm_W: U.comm_W,
+      comm_E: *comm_E,
+      T: E::Scalar::ZERO,
+      u: E::Scalar::ONE,
+      X: U.X.clone(),
+    }
+  }
+
+  /// Returns the number of scalars the instance is absorbed as, and allocated as in the verifier circuit,
+  /// for `num_io` public inputs: each commitment takes the `BN_N_LIMBS` limbs of both coordinates
+  /// and an infinity flag, followed by `T`, `u`, and `X`
+  pub const fn field_element_count(num_io: usize) -> usize {
+    2 * (2 * BN_N_LIMBS + 1) + 2 + num_io
+  }
+
+  /// Checks if the instance is the initial accumulator created by `FoldedInstance::default`,
+  /// i.e., no instance has been folded into it yet
+  pub fn is_trivial(&self) -> bool {
+    self.comm_W == E::CE::commitment_to_zero()
+      && self.comm_E == E::CE::commitment_to_zero()
+      && self.T == E::Scalar::ZERO
+      && self.u == E::Scalar::ZERO
+      && self.X.iter().all(|x| *x == E::Scalar::ZERO)
+  }
+
+  /// Checks that the running claim `T` equals the sum-check claim recomputed from `W`, i.e.,
+  /// `T = sum_x E(x) * (Az(x) * Bz(x) - Cz(x))`, where `E` is the outer product of the two halves of `W.E`.
+  /// Returns `NovaError::InvalidInputLength` if `W.E` is too short to hold both halves
+  pub fn T_accumulation_invariant(
+    &self,
+    S: &Structure<E>,
+    W: &FoldedWitness<E>,
+  ) -> Result<(), NovaError> {
+    if W.E.len() < S.left + S.right {
+      return Err(NovaError::InvalidInputLength);
+    }
+
+    let z = [W.W.clone(), vec![self.u], self.X.clone()].concat();
+    let (Az, Bz, Cz) = S.S.multiply_vec(&z)?;
+
+    // full_E is the outer outer product of E1 and E2
+    // E1 and E2 are splits of E
+    let (E1, E2) = W.E.split_at(S.left);
+    let mut full_E = vec![E::Scalar::ONE; S.left * S.right];
+    for i in 0..S.right {
+      for j in 0..S.left {
+        full_E[i * S.left + j] = E2[i] * E1[j];
+      }
+    }
+
+    let sum = full_E
+      .par_iter()
+      .zip(Az.par_iter())
+      .zip(Bz.par_iter())
+      .zip(Cz.par_iter())
+      .map(|(((e, a), b), c)| *e * ((*a) * (*b) - *c))
+      .reduce(|| E::Scalar::ZERO, |acc, x| acc + x);
+
+    if sum != self.T {
+      return Err(NovaError::UnSat {
+        reason: format!("sum != U.T: sum = {:?}, U.T = {:?}", sum, self.T),
+      });
+    }
+
+    Ok(())
+  }
+
+  /// Checks that `W` opens the commitments `comm_W` and `comm_E` held by the instance
+  pub fn verify_against_witness(
+    &self,
+    ck: &CommitmentKey<E>,
+    W: &FoldedWitness<E>,
+  ) -> Result<(), NovaError> {
+    let (comm_W, comm_E) = rayon::join(
+      || E::CE::try_commit(ck, &W.W, &W.r_W),
+      || E::CE::try_commit(ck, &W.E, &W.r_E),
+    );
+
+    if comm_W? != self.comm_W || comm_E? != self.comm_E {
+      return Err(NovaError::UnSat {
+        reason: "comm_W != U.comm_W || comm_E != U.comm_E".to_string(),
+      });
+    }
+
+    Ok(())
+  }
+
   /// Fold the instance with another instance
   pub fn fold(
     &self,
@@ -178,6 +439,10 @@ impl<E: Engine> FoldedInstance<E> {
     r_b: &E::Scalar,
     T_out: &E::Scalar,
   ) -> Result<Self, NovaError> {
+    if self.X.len() != U2.X.len() {
+      return Err(NovaError::InvalidInputLength);
+    }
+
     // we need to compute the weighted sum using weights of (1-r_b) and r_b
     let comm_W = self.comm_W * (E::Scalar::ONE - r_b) + U2.comm_W * *r_b;
     let comm_E = self.comm_E * (E::Scalar::ONE - r_b) + *comm_E * *r_b;
@@ -199,6 +464,130 @@ impl<E: Engine> FoldedInstance<E> {
   }
 }
 
+#[cfg(feature = "diagnostics")]
+impl<E: Engine> FoldedWitness<E> {
+  /// Folds as in `fold`, and also reports the sparsity of the inputs and commitments to
+  /// the folded witness, which can be compared against the folded instance
+  pub fn fold_with_diagnostics(
+    &self,
+    ck: &CommitmentKey<E>,
+    W2: &R1CSWitness<E>,
+    E2: &Vec<E::Scalar>,
+    r_E2: &E::Scalar,
+    r_b: &E::Scalar,
+  ) -> Result<(Self, FoldDiagnostics<E>), NovaError> {
+    let hamming_norm = |v: &[E::Scalar]| v.par_iter().filter(|x| !bool::from(x.is_zero())).count();
+
+    let W = self.fold(W2, E2, r_E2, r_b)?;
+    let (comm_W, comm_E) = rayon::join(
+      || E::CE::try_commit(ck, &W.W, &W.r_W),
+      || E::CE::try_commit(ck, &W.E, &W.r_E),
+    );
+
+    let diagnostics = FoldDiagnostics {
+      hamming_norm_W: (hamming_norm(&self.W), hamming_norm(&W2.W)),
+      hamming_norm_E: (hamming_norm(&self.E), hamming_norm(E2)),
+      r_b: *r_b,
+      comm_W: comm_W?,
+      comm_E: comm_E?,
+    };
+
+    Ok((W, diagnostics))
+  }
+}
+
+#[cfg(feature = "json")]
+impl<E: Engine> FoldedInstance<E> {
+  /// Encodes the instance as JSON for debugging. Scalars are lowercase hex strings of their
+  /// canonical encoding, and commitments are base64 strings (standard alphabet, padded) of the
+  /// compressed encoding of their affine point.
+  pub fn to_json(&self) -> serde_json::Value {
+    serde_json::json!({
+      "comm_W": commitment_to_base64::<E>(&self.comm_W),
+      "comm_E": commitment_to_base64::<E>(&self.comm_E),
+      "T": hex::encode(self.T.to_repr()),
+      "u": hex::encode(self.u.to_repr()),
+      "X": self.X.iter().map(|x| hex::encode(x.to_repr())).collect::<Vec<_>>(),
+    })
+  }
+
+  /// Decodes an instance from the JSON produced by `to_json`
+  pub fn from_json(v: &serde_json::Value) -> Result<Self, NovaError> {
+    let field = |key: &str| {
+      v.get(key).ok_or(NovaError::SerializationError {
+        reason: format!("missing field {key}"),
+      })
+    };
+    let string = |key: &str| {
+      field(key)?.as_str().ok_or(NovaError::SerializationError {
+        reason: format!("field {key} is not a string"),
+      })
+    };
+
+    let X = field("X")?
+      .as_array()
+      .ok_or(NovaError::SerializationError {
+        reason: "field X is not an array".to_string(),
+      })?
+      .iter()
+      .map(|x| {
+        x.as_str()
+          .and_then(field_from_hex::<E::Scalar>)
+          .ok_or(NovaError::SerializationError {
+            reason: "invalid entry in X".to_string(),
+          })
+      })
+      .collect::<Result<Vec<_>, _>>()?;
+
+    let scalar = |key: &str| {
+      field_from_hex::<E::Scalar>(string(key)?).ok_or(NovaError::SerializationError {
+        reason: format!("field {key} is not a valid scalar"),
+      })
+    };
+    let commitment = |key: &str| {
+      commitment_from_base64::<E>(string(key)?).ok_or(NovaError::SerializationError {
+        reason: format!("field {key} is not a valid commitment"),
+      })
+    };
+
+    Ok(FoldedInstance {
+      comm_W: commitment("comm_W")?,
+      comm_E: commitment("comm_E")?,
+      T: scalar("T")?,
+      u: scalar("u")?,
+      X,
+    })
+  }
+}
+
+/// Decodes a field element from the lowercase hex string of its canonical encoding
+#[cfg(feature = "json")]
+fn field_from_hex<F: PrimeField>(s: &str) -> Option<F> {
+  let bytes = hex::decode(s).ok()?;
+  let mut repr = F::Repr::default();
+  if bytes.len() != repr.as_ref().len() {
+    return None;
+  }
+  repr.as_mut().copy_from_slice(&bytes);
+  F::from_repr(repr).into()
+}
+
+/// Encodes a commitment as the base64 string of the compressed encoding of its affine point.
+/// The commitments in this crate serialize to exactly these bytes under `bincode`.
+#[cfg(feature = "json")]
+fn commitment_to_base64<E: Engine>(c: &Commitment<E>) -> String {
+  BASE64.encode(bincode::serialize(c).expect("a commitment is always serializable"))
+}
+
+/// Decodes a commitment from the base64 string produced by `commitment_to_base64`,
+/// rejecting points that are not on the curve and trailing bytes
+#[cfg(feature = "json")]
+fn commitment_from_base64<E: Engine>(s: &str) -> Option<Commitment<E>> {
+  let bytes = BASE64.decode(s).ok()?;
+  let c = bincode::deserialize::<Commitment<E>>(&bytes).ok()?;
+  (bincode::serialized_size(&c).ok()? == bytes.len() as u64).then_some(c)
+}
+
 impl<E: Engine> AbsorbInRO2Trait<E> for FoldedInstance<E> {
   fn absorb_in_ro2(&self, ro: &mut E::RO2) {
     self.comm_W.absorb_in_ro2(ro);
@@ -222,6 +611,7 @@ mod tests {
       solver::SatisfyingAssignment,
       Circuit, ConstraintSystem,
     },
+    neutron::circuit::relation::AllocatedFoldedInstance,
     provider::{hyperkzg::EvaluationEngine, Bn256EngineKZG},
     spartan::{direct::DirectCircuit, snark::RelaxedR1CSSNARK},
     spartan::{math::Math, polys::eq::EqPolynomial},
@@ -229,6 +619,19 @@ mod tests {
   };
   use rand::rngs::OsRng;
 
+  #[test]
+  fn test_field_element_count() {
+    type E = Bn256EngineKZG;
+
+    // the augmented circuit allocates a running instance with a single public input
+    let mut cs = SatisfyingAssignment::<E>::new();
+    AllocatedFoldedInstance::<E>::alloc(cs.namespace(|| "U"), None).unwrap();
+    assert_eq!(
+      cs.aux_assignment().len(),
+      FoldedInstance::<E>::field_element_count(1)
+    );
+  }
+
   fn test_sat_inner<E: Engine, S: RelaxedR1CSSNARKTrait<E>>() -> Result<(), NovaError> {
     // generate a non-trivial circuit
     let num_cons: usize = 16;
@@ -246,6 +649,7 @@ mod tests {
     // test default instance-witness pair under the structure
     let W = FoldedWitness::default(&S);
     let U = FoldedInstance::default(&S);
+    assert!(U.is_trivial());
     S.is_sat(&ck, &U, &W)?;
 
     // generate a satisfying instance-witness for the r1cs
@@ -278,13 +682,27 @@ mod tests {
       r_E: E::Scalar::random(&mut OsRng),
     };
 
-    let U = FoldedInstance {
-      comm_W: u.comm_W,
-      comm_E: E::CE::commit(&ck, &E, &W.r_E),
-      T: E::Scalar::ZERO,
-      X: u.X.clone(),
-      u: E::Scalar::ONE,
-    };
+    let U = FoldedInstance::from_r1cs_instance(&u, &E::CE::commit(&ck, &E, &W.r_E));
+    assert!(!U.is_trivial());
+    U.verify_against_witness(&ck, &W)?;
+
+    // a witness that does not open the commitments is rejected
+    assert_eq!(W.e_polynomial(), &E[..]);
+    let mut W_bad = W.clone();
+    W_bad.e_polynomial_mut()[0] += E::Scalar::ONE;
+    assert!(U.verify_against_witness(&ck, &W_bad).is_err());
+
+    // the running claim must match the one recomputed from the witness
+    U.T_accumulation_invariant(&S, &W)?;
+    let mut U_bad = U.clone();
+    U_bad.T += E::Scalar::ONE;
+    assert!(U_bad.T_accumulation_invariant(&S, &W).is_err());
+    let mut W_short = W.clone();
+    W_short.E.truncate(S.left);
+    assert_eq!(
+      U.T_accumulation_invariant(&S, &W_short),
+      Err(NovaError::InvalidInputLength)
+    );
 
     S.is_sat(&ck, &U, &W)
   }
@@ -296,4 +714,235 @@ mod tests {
     let res = test_sat_inner::<E, S>();
     assert!(res.is_ok());
   }
+
+  #[test]
+  fn test_split_into_parties() {
+    type E = Bn256EngineKZG;
+    type F = <E as Engine>::Scalar;
+
+    let random_vec = |len: usize| (0..len).map(|_| F::random(&mut OsRng)).collect::<Vec<_>>();
+    let W = FoldedWitness::<E> {
+      W: random_vec(16),
+      r_W: F::random(&mut OsRng),
+      E: random_vec(8),
+      r_E: F::random(&mut OsRng),
+    };
+
+    let shares = W.split_into_parties(5, 3, &mut OsRng).unwrap();
+    assert_eq!(shares.len(), 5);
+
+    // any subset of threshold shares recovers the witness
+    assert_eq!(
+      FoldedWitness::reconstruct_from_shares(&shares[..3]),
+      Ok(W.clone())
+    );
+    assert_eq!(
+      FoldedWitness::reconstruct_from_shares(&shares[2..]),
+      Ok(W.clone())
+    );
+    let subset = [shares[4].clone(), shares[0].clone(), shares[2].clone()];
+    assert_eq!(
+      FoldedWitness::reconstruct_from_shares(&subset),
+      Ok(W.clone())
+    );
+
+    // fewer than threshold shares are rejected
+    assert_eq!(
+      FoldedWitness::reconstruct_from_shares(&shares[..2]),
+      Err(NovaError::InvalidInputLength)
+    );
+
+    // repeated shares are rejected
+    let repeated = [shares[1].clone(), shares[1].clone(), shares[3].clone()];
+    assert_eq!(
+      FoldedWitness::reconstruct_from_shares(&repeated),
+      Err(NovaError::InvalidIndex)
+    );
+
+    // a threshold of one gives every party the witness itself
+    let shares = W.split_into_parties(2, 1, &mut OsRng).unwrap();
+    assert_eq!(shares[1].W, W.W);
+    assert_eq!(
+      FoldedWitness::reconstruct_from_shares(&shares[1..]),
+      Ok(W.clone())
+    );
+
+    // a threshold outside 1..=n_parties is rejected
+    assert_eq!(
+      W.split_into_parties(3, 0, &mut OsRng).err(),
+      Some(NovaError::InvalidInputLength)
+    );
+    assert_eq!(
+      W.split_into_parties(3, 4, &mut OsRng).err(),
+      Some(NovaError::InvalidInputLength)
+    );
+  }
+
+  #[test]
+  fn test_reduce_blinding() {
+    type E = Bn256EngineKZG;
+    type F = <E as Engine>::Scalar;
+
+    let random_vec = |len: usize| (0..len).map(|_| F::random(&mut OsRng)).collect::<Vec<_>>();
+    let ck = <E as Engine>::CE::setup(b"test", 16);
+    let W1 = FoldedWitness::<E> {
+      W: random_vec(16),
+      r_W: F::random(&mut OsRng),
+      E: random_vec(8),
+      r_E: F::random(&mut OsRng),
+    };
+    let W2 = R1CSWitness::<E> {
+      W: random_vec(16),
+      r_W: F::random(&mut OsRng),
+    };
+    let (E2, r_E2) = (random_vec(8), F::random(&mut OsRng));
+    let r_b = F::random(&mut OsRng);
+
+    let W = W1.fold(&W2, &E2, &r_E2, &r_b).unwrap();
+    let (r_W, r_E) = W1.reduce_blinding(&W2, &r_E2, &r_b);
+    assert_eq!((W.r_W, W.r_E), (r_W, r_E));
+
+    // the commitments to the folded witness under the combined blinds are the folded commitments
+    let commit = |v: &[F], r: &F| <E as Engine>::CE::commit(&ck, v, r);
+    assert_eq!(
+      commit(&W.W, &r_W),
+      commit(&W1.W, &W1.r_W) * (F::ONE - r_b) + commit(&W2.W, &W2.r_W) * r_b
+    );
+    assert_eq!(
+      commit(&W.E, &r_E),
+      commit(&W1.E, &W1.r_E) * (F::ONE - r_b) + commit(&E2, &r_E2) * r_b
+    );
+  }
+
+  #[cfg(feature = "json")]
+  fn test_json_with<E: Engine>() {
+    let random_vec = |len: usize| {
+      (0..len)
+        .map(|_| E::Scalar::random(&mut OsRng))
+        .collect::<Vec<_>>()
+    };
+    let ck = E::CE::setup(b"test", 4);
+    let U = FoldedInstance::<E> {
+      comm_W: E::CE::commit(&ck, &random_vec(4), &E::Scalar::random(&mut OsRng)),
+      comm_E: E::CE::commitment_to_zero(),
+      T: E::Scalar::random(&mut OsRng),
+      u: E::Scalar::random(&mut OsRng),
+      X: random_vec(2),
+    };
+
+    let json = U.to_json();
+    assert_eq!(FoldedInstance::<E>::from_json(&json), Ok(U.clone()));
+
+    // commitments are the base64 of a compressed point, i.e., a single 32-byte coordinate
+    let comm_W = BASE64.decode(json["comm_W"].as_str().unwrap()).unwrap();
+    assert_eq!(comm_W.len(), 32);
+
+    // the JSON survives a round trip through its text form
+    let text = json.to_string();
+    let parsed: serde_json::Value = serde_json::from_str(&text).unwrap();
+    assert_eq!(FoldedInstance::<E>::from_json(&parsed), Ok(U));
+
+    let mut bad = json.clone();
+    bad["T"] = serde_json::Value::String("zz".to_string());
+    assert!(FoldedInstance::<E>::from_json(&bad).is_err());
+    let mut bad = json.clone();
+    bad["comm_W"] = serde_json::Value::String(BASE64.encode([comm_W, vec![0]].concat()));
+    assert!(FoldedInstance::<E>::from_json(&bad).is_err());
+    let mut bad = json;
+    bad.as_object_mut().unwrap().remove("comm_W");
+    assert!(FoldedInstance::<E>::from_json(&bad).is_err());
+  }
+
+  #[cfg(feature = "json")]
+  #[test]
+  fn test_json() {
+    test_json_with::<Bn256EngineKZG>();
+    test_json_with::<crate::provider::PallasEngine>();
+  }
+
+  #[cfg(feature = "diagnostics")]
+  #[test]
+  fn test_fold_with_diagnostics() {
+    type E = Bn256EngineKZG;
+    type F = <E as Engine>::Scalar;
+
+    let ck = <E as Engine>::CE::setup(b"test", 16);
+    let W1 = FoldedWitness::<E> {
+      W: (0..16).map(F::from).collect(),
+      r_W: F::random(&mut OsRng),
+      E: vec![F::ONE; 8],
+      r_E: F::random(&mut OsRng),
+    };
+    let W2 = R1CSWitness::<E> {
+      W: vec![F::ZERO; 16],
+      r_W: F::random(&mut OsRng),
+    };
+    let E2 = vec![F::ZERO; 8];
+    let (r_E2, r_b) = (F::random(&mut OsRng), F::random(&mut OsRng));
+
+    let (W, diagnostics) = W1
+      .fold_with_diagnostics(&ck, &W2, &E2, &r_E2, &r_b)
+      .unwrap();
+    assert_eq!(W, W1.fold(&W2, &E2, &r_E2, &r_b).unwrap());
+    assert_eq!(diagnostics.hamming_norm_W, (15, 0));
+    assert_eq!(diagnostics.hamming_norm_E, (8, 0));
+    assert_eq!(diagnostics.r_b, r_b);
+    assert_eq!(
+      diagnostics.comm_W,
+      <E as Engine>::CE::commit(&ck, &W.W, &W.r_W)
+    );
+    assert_eq!(
+      diagnostics.comm_E,
+      <E as Engine>::CE::commit(&ck, &W.E, &W.r_E)
+    );
+  }
+
+  #[test]
+  fn test_fold_length_mismatch() {
+    type E = Bn256EngineKZG;
+    type F = <E as Engine>::Scalar;
+
+    let random_vec = |len: usize| (0..len).map(|_| F::random(&mut OsRng)).collect::<Vec<_>>();
+    let r_b = F::random(&mut OsRng);
+
+    // folding never truncates to the shorter of the two inputs
+    let U1 = FoldedInstance::<E> {
+      comm_W: <E as Engine>::CE::commitment_to_zero(),
+      comm_E: <E as Engine>::CE::commitment_to_zero(),
+      T: F::ZERO,
+      u: F::ZERO,
+      X: random_vec(2),
+    };
+    let U2 = R1CSInstance::<E> {
+      comm_W: <E as Engine>::CE::commitment_to_zero(),
+      X: random_vec(1),
+    };
+    assert_eq!(
+      U1.fold(&U2, &U1.comm_E, &r_b, &F::ZERO),
+      Err(NovaError::InvalidInputLength)
+    );
+
+    let W1 = FoldedWitness::<E> {
+      W: random_vec(16),
+      r_W: F::ZERO,
+      E: random_vec(8),
+      r_E: F::ZERO,
+    };
+    let W2 = R1CSWitness::<E> {
+      W: random_vec(15),
+      r_W: F::ZERO,
+    };
+    assert_eq!(
+      W1.fold(&W2, &random_vec(8), &F::ZERO, &r_b),
+      Err(NovaError::InvalidWitnessLength)
+    );
+    let W2 = R1CSWitness::<E> {
+      W: random_vec(16),
+      r_W: F::ZERO,
+    };
+    assert_eq!(
+      W1.fold(&W2, &random_vec(7), &F::ZERO, &r_b),
+      Err(NovaError::InvalidInputLength)
+    );
+  }
 }
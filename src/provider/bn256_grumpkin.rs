@@ -3,7 +3,7 @@ use crate::{
   impl_traits,
   provider::{
     msm::cpu_best_msm,
-    traits::{DlogGroup, PairingGroup},
+    traits::{DlogGroup, HashToGroup, PairingGroup},
   },
   traits::{Group, PrimeFieldExt, TranscriptReprTrait},
 };
@@ -111,6 +111,12 @@ impl DlogGroup for G2 {
   }
 }
 
+impl HashToGroup for G2 {
+  fn hash_to_group(msg: &[u8]) -> Self {
+    G2::hash_to_curve("from_uniform_bytes")(msg)
+  }
+}
+
 impl<G: DlogGroup> TranscriptReprTrait<G> for G2Compressed {
   fn to_transcript_bytes(&self) -> Vec<u8> {
     self.as_ref().to_vec()
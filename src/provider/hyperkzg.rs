@@ -76,6 +76,11 @@ where
   pub fn tau_H(&self) -> &<<E::GE as PairingGroup>::G2 as DlogGroup>::AffineGroupElement {
     &self.tau_H
   }
+
+  /// Returns the maximum number of entries in a vector that this key can commit to
+  pub fn max_vars(&self) -> usize {
+    self.ck.len()
+  }
 }
 
 impl<E: Engine> Len for CommitmentKey<E>
@@ -127,6 +132,10 @@ where
   fn to_coordinates(&self) -> (E::Base, E::Base, bool) {
     self.comm.to_coordinates()
   }
+
+  fn from_coordinates(x: E::Base, y: E::Base, is_infinity: bool) -> Option<Self> {
+    E::GE::from_coordinates(x, y, is_infinity).map(|comm| Commitment { comm })
+  }
 }
 
 impl<E: Engine> CommitmentKey<E>
@@ -1154,4 +1163,11 @@ mod tests {
       assert_eq!(ck.ck[i], read_ck.ck[i]);
     }
   }
+
+  #[test]
+  fn test_commitment_to_zero() {
+    let ck = CommitmentEngine::<E>::setup(b"test", 8);
+    let comm = CommitmentEngine::<E>::commit(&ck, &[Fr::ZERO; 8], &Fr::ZERO);
+    assert_eq!(CommitmentEngine::<E>::commitment_to_zero(), comm);
+  }
 }
@@ -179,7 +179,7 @@ where
   ) -> Result<Self, NovaError> {
     transcript.dom_sep(Self::protocol_name());
 
-    let (ck, _) = ck.split_at(U.b_vec.len());
+    let (ck, _) = ck.split_at(U.b_vec.len())?;
 
     if U.b_vec.len() != W.a_vec.len() {
       return Err(NovaError::InvalidInputLength);
@@ -208,7 +208,7 @@ where
       NovaError,
     > {
       let n = a_vec.len();
-      let (ck_L, ck_R) = ck.split_at(n / 2);
+      let (ck_L, ck_R) = ck.split_at(n / 2)?;
 
       let c_L = inner_product(&a_vec[0..n / 2], &b_vec[n / 2..n]);
       let c_R = inner_product(&a_vec[n / 2..n], &b_vec[0..n / 2]);
@@ -290,7 +290,7 @@ where
     U: &InnerProductInstance<E>,
     transcript: &mut E::TE,
   ) -> Result<(), NovaError> {
-    let (ck, _) = ck.split_at(U.b_vec.len());
+    let (ck, _) = ck.split_at(U.b_vec.len())?;
 
     transcript.dom_sep(Self::protocol_name());
     if U.b_vec.len() != n
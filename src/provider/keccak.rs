@@ -106,7 +106,7 @@ mod tests {
   };
   use ff::PrimeField;
   use rand::Rng;
-  use sha3::{Digest, Keccak256};
+  use sha3::{Digest, Keccak256, Sha3_256};
 
   fn test_keccak_transcript_with<E: Engine>(expected_h1: &'static str, expected_h2: &'static str) {
     let mut transcript: Keccak256Transcript<E> = Keccak256Transcript::new(b"test");
@@ -152,6 +152,61 @@ mod tests {
     );
   }
 
+  fn test_absorb_bytes_with<E: Engine>() {
+    let challenge = |chunks: &[&[u8]]| {
+      let mut transcript: Keccak256Transcript<E> = Keccak256Transcript::new(b"test");
+      for chunk in chunks {
+        transcript.absorb_bytes(b"session", chunk);
+      }
+      transcript.squeeze(b"c").unwrap()
+    };
+
+    assert_eq!(challenge(&[b"abc"]), challenge(&[b"abc"]));
+    assert_ne!(challenge(&[b"abc"]), challenge(&[b"abd"]));
+
+    // the length prefix separates different splits of the same bytes
+    assert_ne!(challenge(&[b"ab", b"c"]), challenge(&[b"a", b"bc"]));
+  }
+
+  #[test]
+  fn test_absorb_bytes() {
+    test_absorb_bytes_with::<PallasEngine>();
+    test_absorb_bytes_with::<Bn256EngineKZG>();
+    test_absorb_bytes_with::<Secp256k1Engine>();
+  }
+
+  fn test_squeeze_bytes_with<E: Engine>() {
+    let squeeze_bytes = |len: usize| {
+      let mut transcript: Keccak256Transcript<E> = Keccak256Transcript::new(b"test");
+      let mut output = vec![0u8; len];
+      transcript.squeeze_bytes(b"c", &mut output).unwrap();
+      (output, transcript.squeeze(b"c").unwrap())
+    };
+
+    // the output is deterministic and a shorter request is a prefix of a longer one
+    let (short, _) = squeeze_bytes(16);
+    let (long, next) = squeeze_bytes(100);
+    assert_eq!(short, squeeze_bytes(16).0);
+    assert_eq!(short[..], long[..16]);
+    assert!(long.iter().any(|b| *b != 0));
+
+    // the transcript advances, and the seed differs from a plain squeeze under the same label
+    let mut transcript: Keccak256Transcript<E> = Keccak256Transcript::new(b"test");
+    let c = transcript.squeeze(b"c").unwrap();
+    assert_ne!(c, next);
+    assert_ne!(
+      long[..32],
+      Sha3_256::digest(c.to_repr().as_ref()).as_slice()[..]
+    );
+  }
+
+  #[test]
+  fn test_squeeze_bytes() {
+    test_squeeze_bytes_with::<PallasEngine>();
+    test_squeeze_bytes_with::<Bn256EngineKZG>();
+    test_squeeze_bytes_with::<Secp256k1Engine>();
+  }
+
   #[test]
   fn test_keccak_example() {
     let mut hasher = Keccak256::new();
@@ -2,7 +2,10 @@
 use crate::{
   errors::NovaError,
   gadgets::utils::to_bignat_repr,
-  provider::{ptau::read_points, traits::DlogGroup},
+  provider::{
+    ptau::read_points,
+    traits::{DlogGroup, HashToGroup},
+  },
   traits::{
     commitment::{CommitmentEngineTrait, CommitmentTrait, Len},
     AbsorbInRO2Trait, AbsorbInROTrait, Engine, ROTrait, TranscriptReprTrait,
@@ -63,6 +66,10 @@ where
   fn to_coordinates(&self) -> (E::Base, E::Base, bool) {
     self.comm.to_coordinates()
   }
+
+  fn from_coordinates(x: E::Base, y: E::Base, is_infinity: bool) -> Option<Self> {
+    E::GE::from_coordinates(x, y, is_infinity).map(|comm| Commitment { comm })
+  }
 }
 
 impl<E: Engine> Default for Commitment<E>
@@ -196,6 +203,38 @@ where
     points.extend(self.ck.iter().cloned());
     write_points(writer, points)
   }
+
+  /// Derives a commitment key for vectors of up to `max_vars` entries from a public `seed`,
+  /// as a nothing-up-my-sleeve alternative to `CommitmentEngineTrait::setup` for seeds that are
+  /// only known at runtime. Like `setup`, it produces `max_vars.next_power_of_two()` generators
+  /// plus the blinding generator `h`.
+  ///
+  /// Generator `i` (with `h` at index 0) is the hash of `seed || i`, where `i` is a little-endian `u64`,
+  /// under the curve's RFC 9380 hash-to-curve suite from `halo2curves`, which expands the message
+  /// with SHA-256 and maps to the curve with the Shallue-van de Woestijne method (BN254, Grumpkin)
+  /// or simplified SWU through an isogeny (Pallas, Vesta, secp256k1, secq256k1). The domain prefix
+  /// is the one `setup` uses.
+  pub fn from_uniform_bytes(seed: &[u8], max_vars: usize) -> Self
+  where
+    E::GE: HashToGroup,
+  {
+    let gens = (0..max_vars.next_power_of_two() + 1)
+      .into_par_iter()
+      .map(|i| E::GE::hash_to_group(&[seed, &(i as u64).to_le_bytes()].concat()).affine())
+      .collect::<Vec<_>>();
+
+    let (h, ck) = gens.split_first().unwrap();
+
+    Self {
+      ck: ck.to_vec(),
+      h: Some(*h),
+    }
+  }
+
+  /// Returns the maximum number of entries in a vector that this key can commit to
+  pub fn max_vars(&self) -> usize {
+    self.ck.len()
+  }
 }
 
 impl<E: Engine> CommitmentEngineTrait<E> for CommitmentEngine<E>
@@ -227,10 +266,10 @@ where
   fn commit(ck: &Self::CommitmentKey, v: &[E::Scalar], r: &E::Scalar) -> Self::Commitment {
     assert!(ck.ck.len() >= v.len());
 
-    if ck.h.is_some() {
+    if let Some(h) = &ck.h {
       Commitment {
         comm: E::GE::vartime_multiscalar_mul(v, &ck.ck[..v.len()])
-          + <E::GE as DlogGroup>::group(ck.h.as_ref().unwrap()) * r,
+          + <E::GE as DlogGroup>::group(h) * r,
       }
     } else {
       assert_eq!(*r, E::Scalar::ZERO);
@@ -280,8 +319,10 @@ pub trait CommitmentKeyExtTrait<E: Engine>
 where
   E::GE: DlogGroup,
 {
-  /// Splits the commitment key into two pieces at a specified point
-  fn split_at(&self, n: usize) -> (Self, Self)
+  /// Splits the commitment key into two pieces at a specified point,
+  /// covering the generators in `[0, n)` and `[n, len)`; both pieces keep the blinding generator.
+  /// Returns `NovaError::InvalidInputLength` if `n` exceeds the length of the key.
+  fn split_at(&self, n: usize) -> Result<(Self, Self), NovaError>
   where
     Self: Sized;
 
@@ -307,8 +348,12 @@ where
   E: Engine<CE = CommitmentEngine<E>>,
   E::GE: DlogGroup,
 {
-  fn split_at(&self, n: usize) -> (CommitmentKey<E>, CommitmentKey<E>) {
-    (
+  fn split_at(&self, n: usize) -> Result<(CommitmentKey<E>, CommitmentKey<E>), NovaError> {
+    if n > self.ck.len() {
+      return Err(NovaError::InvalidInputLength);
+    }
+
+    Ok((
       CommitmentKey {
         ck: self.ck[0..n].to_vec(),
         h: self.h,
@@ -317,7 +362,7 @@ where
         ck: self.ck[n..].to_vec(),
         h: self.h,
       },
-    )
+    ))
   }
 
   fn combine(&self, other: &CommitmentKey<E>) -> CommitmentKey<E> {
@@ -332,12 +377,12 @@ where
   // combines the left and right halves of `self` using `w1` and `w2` as the weights
   fn fold(&self, w1: &E::Scalar, w2: &E::Scalar) -> CommitmentKey<E> {
     let w = vec![*w1, *w2];
-    let (L, R) = self.split_at(self.ck.len() / 2);
+    let (L, R) = self.ck.split_at(self.ck.len() / 2);
 
     let ck = (0..self.ck.len() / 2)
       .into_par_iter()
       .map(|i| {
-        let bases = [L.ck[i], R.ck[i]].to_vec();
+        let bases = [L[i], R[i]].to_vec();
         E::GE::vartime_multiscalar_mul(&w, &bases).affine()
       })
       .collect();
@@ -382,6 +427,7 @@ mod tests {
   use super::*;
 
   use crate::{provider::GrumpkinEngine, CommitmentKey};
+  use rand_core::OsRng;
   use std::{fs::File, io::BufWriter};
 
   type E = GrumpkinEngine;
@@ -404,4 +450,101 @@ mod tests {
     assert_eq!(keys_read.h, keys.h);
     assert_eq!(keys_read.ck, keys.ck);
   }
+
+  #[test]
+  fn test_from_uniform_bytes() {
+    let ck = CommitmentKey::<E>::from_uniform_bytes(b"seed", 5);
+    assert_eq!(ck.ck.len(), 8);
+    assert_eq!(ck, CommitmentKey::<E>::from_uniform_bytes(b"seed", 5));
+
+    // distinct generators, none of them the identity
+    let zero = <E as Engine>::GE::zero().affine();
+    let gens = [vec![ck.h.unwrap()], ck.ck.clone()].concat();
+    assert!(gens.iter().all(|g| *g != zero));
+    assert!((0..gens.len()).all(|i| (0..i).all(|j| gens[i] != gens[j])));
+
+    let other = CommitmentKey::<E>::from_uniform_bytes(b"seed2", 5);
+    assert!(ck.ck.iter().zip(other.ck.iter()).all(|(g1, g2)| g1 != g2));
+    assert_ne!(ck.h, other.h);
+  }
+
+  #[test]
+  fn test_commitment_to_zero() {
+    let ck = CommitmentEngine::<E>::setup(b"test", 8);
+    let zero = <E as Engine>::Scalar::ZERO;
+    let comm = CommitmentEngine::<E>::commit(&ck, &[zero; 8], &zero);
+    assert_eq!(CommitmentEngine::<E>::commitment_to_zero(), comm);
+  }
+
+  #[test]
+  fn test_multi_scalar_mul() {
+    type F = <E as Engine>::Scalar;
+
+    let n = 8;
+    let ck = CommitmentEngine::<E>::setup(b"test", n);
+    let dk = CommitmentEngine::<E>::derand_key(&ck);
+
+    // the MSM is the commitment with its blind removed
+    let v = (0..n).map(|_| F::random(&mut OsRng)).collect::<Vec<_>>();
+    let r = F::random(&mut OsRng);
+    let comm = CommitmentEngine::<E>::commit(&ck, &v, &r);
+    assert_eq!(
+      CommitmentEngine::<E>::multi_scalar_mul(&ck, &v),
+      Ok(CommitmentEngine::<E>::derandomize(&dk, &comm, &r))
+    );
+
+    assert_eq!(
+      CommitmentEngine::<E>::multi_scalar_mul(&ck, &[F::ONE; 9]),
+      Err(NovaError::CommitmentKeyTooSmall {
+        required: 9,
+        available: 8
+      })
+    );
+  }
+
+  #[test]
+  fn test_try_commit() {
+    type F = <E as Engine>::Scalar;
+
+    let ck = CommitmentEngine::<E>::setup(b"test", 8);
+    let v = (0..8).map(|_| F::random(&mut OsRng)).collect::<Vec<_>>();
+    let r = F::random(&mut OsRng);
+    assert_eq!(
+      CommitmentEngine::<E>::try_commit(&ck, &v, &r),
+      Ok(CommitmentEngine::<E>::commit(&ck, &v, &r))
+    );
+    assert_eq!(
+      CommitmentEngine::<E>::try_commit(&ck, &[v.clone(), v].concat(), &r),
+      Err(NovaError::CommitmentKeyTooSmall {
+        required: 16,
+        available: ck.max_vars()
+      })
+    );
+  }
+
+  #[test]
+  fn test_split_at() {
+    type F = <E as Engine>::Scalar;
+
+    let n = 16;
+    let split_point = 5;
+    let ck = CommitmentEngine::<E>::setup(b"test", n);
+    let (ck1, ck2) = ck.split_at(split_point).unwrap();
+    assert_eq!(ck1.length(), split_point);
+    assert_eq!(ck2.length(), n - split_point);
+
+    // commitments under the two pieces add up to the commitment under the full key
+    let w = (0..n).map(|_| F::random(&mut OsRng)).collect::<Vec<_>>();
+    let r = F::random(&mut OsRng);
+    let comm = CommitmentEngine::<E>::commit(&ck, &w, &r);
+    let comm1 = CommitmentEngine::<E>::commit(&ck1, &w[..split_point], &r);
+    let comm2 = CommitmentEngine::<E>::commit(&ck2, &w[split_point..], &F::ZERO);
+    assert_eq!(comm, comm1 + comm2);
+
+    // a split point past the end of the key is rejected
+    assert!(matches!(
+      ck.split_at(n + 1),
+      Err(NovaError::InvalidInputLength)
+    ));
+  }
 }
@@ -80,6 +80,25 @@ pub trait DlogGroup:
 
   /// Returns the affine coordinates (x, y, infinity) for the point
   fn to_coordinates(&self) -> (<Self as Group>::Base, <Self as Group>::Base, bool);
+
+  /// Returns the point with the given affine coordinates (x, y, infinity),
+  /// or `None` if they do not describe a point on the curve.
+  ///
+  /// The default returns `None`, for groups whose points do not have coordinates in `Base`
+  fn from_coordinates(
+    _x: <Self as Group>::Base,
+    _y: <Self as Group>::Base,
+    _is_infinity: bool,
+  ) -> Option<Self> {
+    None
+  }
+}
+
+/// A trait that defines extensions to the DlogGroup trait, to be implemented for
+/// groups with a hash-to-curve map
+pub trait HashToGroup: DlogGroup {
+  /// Hashes `msg` to a group element
+  fn hash_to_group(msg: &[u8]) -> Self;
 }
 
 /// A trait that defines extensions to the DlogGroup trait, to be implemented for
@@ -201,6 +220,21 @@ macro_rules! impl_traits {
           (Self::Base::zero(), Self::Base::zero(), true)
         }
       }
+
+      fn from_coordinates(x: Self::Base, y: Self::Base, is_infinity: bool) -> Option<Self> {
+        if is_infinity {
+          Some($name::Point::identity())
+        } else {
+          Option::<$name_curve_affine>::from($name_curve_affine::from_xy(x, y))
+            .map($name::Point::from)
+        }
+      }
+    }
+
+    impl HashToGroup for $name::Point {
+      fn hash_to_group(msg: &[u8]) -> Self {
+        $name_curve::hash_to_curve("from_uniform_bytes")(msg)
+      }
     }
 
     impl PrimeFieldExt for $name::Scalar {
@@ -14,15 +14,22 @@ use crate::{
   Commitment, CommitmentKey, DerandKey, CE,
 };
 use core::cmp::max;
-use ff::Field;
+use ff::{Field, PrimeField};
 use once_cell::sync::OnceCell;
 use rand_core::OsRng;
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 mod sparse;
 pub(crate) use sparse::SparseMatrix;
 
+/// A dense matrix, stored row by row
+pub type DenseMatrix<F> = Vec<Vec<F>>;
+
+/// A constraint reported unsatisfied by `R1CSShape::hadamard_check`, as `(i, Az[i], Bz[i], Cz[i])`
+pub type ConstraintFailure<F> = (usize, F, F, F);
+
 /// A type that holds the shape of the R1CS matrices
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct R1CSShape<E: Engine> {
@@ -84,27 +91,7 @@ impl<E: Engine> R1CSShape<E> {
     B: SparseMatrix<E::Scalar>,
     C: SparseMatrix<E::Scalar>,
   ) -> Result<R1CSShape<E>, NovaError> {
-    let is_valid = |num_cons: usize,
-                    num_vars: usize,
-                    num_io: usize,
-                    M: &SparseMatrix<E::Scalar>|
-     -> Result<Vec<()>, NovaError> {
-      M.iter()
-        .map(|(row, col, _val)| {
-          if row >= num_cons || col > num_io + num_vars {
-            Err(NovaError::InvalidIndex)
-          } else {
-            Ok(())
-          }
-        })
-        .collect::<Result<Vec<()>, NovaError>>()
-    };
-
-    is_valid(num_cons, num_vars, num_io, &A)?;
-    is_valid(num_cons, num_vars, num_io, &B)?;
-    is_valid(num_cons, num_vars, num_io, &C)?;
-
-    Ok(R1CSShape {
+    let S = R1CSShape {
       num_cons,
       num_vars,
       num_io,
@@ -112,7 +99,70 @@ impl<E: Engine> R1CSShape<E> {
       B,
       C,
       digest: OnceCell::new(),
-    })
+    };
+    S.validate()?;
+
+    Ok(S)
+  }
+
+  /// Checks that every entry of `A`, `B`, and `C` lies within `[0, num_cons) x [0, num_vars + num_io + 1)`,
+  /// returning `NovaError::InvalidIndex` otherwise.
+  ///
+  /// `R1CSShape::new` performs this check; it is exposed for shapes obtained by other means, e.g., deserialization.
+  pub fn validate(&self) -> Result<(), NovaError> {
+    let is_valid = |M: &SparseMatrix<E::Scalar>| {
+      M.iter()
+        .all(|(row, col, _val)| row < self.num_cons && col <= self.num_io + self.num_vars)
+    };
+
+    if is_valid(&self.A) && is_valid(&self.B) && is_valid(&self.C) {
+      Ok(())
+    } else {
+      Err(NovaError::InvalidIndex)
+    }
+  }
+
+  /// Create an object of type `R1CSShape` from R1CS matrices given as `(row, col, value)` entries,
+  /// in any order.
+  ///
+  /// Unlike `SparseMatrix::new`, which panics on malformed input, this returns
+  /// `NovaError::InvalidIndex` if an entry lies outside `[0, num_cons) x [0, num_vars + num_io + 1)`
+  /// or if a matrix has two entries at the same position.
+  pub fn from_sparse_matrices(
+    num_cons: usize,
+    num_vars: usize,
+    num_io: usize,
+    A: &[(usize, usize, E::Scalar)],
+    B: &[(usize, usize, E::Scalar)],
+    C: &[(usize, usize, E::Scalar)],
+  ) -> Result<R1CSShape<E>, NovaError> {
+    let cols = num_vars + num_io + 1;
+    let to_matrix = |M: &[(usize, usize, E::Scalar)]| {
+      if M
+        .iter()
+        .any(|(row, col, _)| *row >= num_cons || *col >= cols)
+      {
+        return Err(NovaError::InvalidIndex);
+      }
+
+      // `SparseMatrix::new` expects the entries of every row to have increasing columns
+      let mut M = M.to_vec();
+      M.sort_by_key(|(row, col, _)| (*row, *col));
+      if M.windows(2).any(|w| (w[0].0, w[0].1) == (w[1].0, w[1].1)) {
+        return Err(NovaError::InvalidIndex);
+      }
+
+      Ok(SparseMatrix::new(&M, num_cons, cols))
+    };
+
+    Self::new(
+      num_cons,
+      num_vars,
+      num_io,
+      to_matrix(A)?,
+      to_matrix(B)?,
+      to_matrix(C)?,
+    )
   }
 
   /// Generates public parameters for a Rank-1 Constraint System (R1CS).
@@ -193,10 +243,10 @@ impl<E: Engine> R1CSShape<E> {
     // verify if comm_E and comm_W are commitments to E and W
     let res_comm = {
       let (comm_W, comm_E) = rayon::join(
-        || CE::<E>::commit(ck, &W.W, &W.r_W),
-        || CE::<E>::commit(ck, &W.E, &W.r_E),
+        || CE::<E>::try_commit(ck, &W.W, &W.r_W),
+        || CE::<E>::try_commit(ck, &W.E, &W.r_E),
       );
-      U.comm_W == comm_W && U.comm_E == comm_E
+      U.comm_W == comm_W? && U.comm_E == comm_E?
     };
 
     if !res_eq {
@@ -214,6 +264,27 @@ impl<E: Engine> R1CSShape<E> {
     Ok(())
   }
 
+  /// Checks the length consistency of `U` and `W` with the shape, i.e., that `W` holds exactly
+  /// `num_vars` private variables and `U.X` exactly `num_io` public inputs, which together occupy
+  /// `z = (W, 1, U.X)`. Only lengths are compared: values placed in the wrong positions with the
+  /// right lengths are not detected here.
+  ///
+  /// Returns `NovaError::InvalidWitnessLength` or `NovaError::InvalidInputLength` on a mismatch.
+  pub fn check_public_io_consistency(
+    &self,
+    U: &R1CSInstance<E>,
+    W: &R1CSWitness<E>,
+  ) -> Result<(), NovaError> {
+    if W.W.len() != self.num_vars {
+      return Err(NovaError::InvalidWitnessLength);
+    }
+    if U.X.len() != self.num_io {
+      return Err(NovaError::InvalidInputLength);
+    }
+
+    Ok(())
+  }
+
   /// Checks if the R1CS instance is satisfiable given a witness and its shape
   pub fn is_sat(
     &self,
@@ -236,7 +307,7 @@ impl<E: Engine> R1CSShape<E> {
     };
 
     // verify if comm_W is a commitment to W
-    let res_comm = U.comm_W == CE::<E>::commit(ck, &W.W, &W.r_W);
+    let res_comm = U.comm_W == CE::<E>::try_commit(ck, &W.W, &W.r_W)?;
 
     if !res_eq {
       return Err(NovaError::UnSat {
@@ -253,6 +324,149 @@ impl<E: Engine> R1CSShape<E> {
     Ok(())
   }
 
+  /// Evaluates every constraint on `z = (W, 1, X)` and reports all that fail, rather than
+  /// stopping at the first one. Intended for debugging circuits.
+  ///
+  /// The inner result is `Ok(())` if `z` satisfies every constraint, and otherwise `Err` with
+  /// each failure as `(i, Az[i], Bz[i], Cz[i])`. The outer result is
+  /// `NovaError::InvalidWitnessLength` if `z` does not have `num_vars + 1 + num_io` entries.
+  pub fn hadamard_check(
+    &self,
+    z: &[E::Scalar],
+  ) -> Result<Result<(), Vec<ConstraintFailure<E::Scalar>>>, NovaError> {
+    let (Az, Bz, Cz) = self.multiply_vec(z)?;
+
+    let failures = (0..self.num_cons)
+      .filter(|&i| Az[i] * Bz[i] != Cz[i])
+      .map(|i| (i, Az[i], Bz[i], Cz[i]))
+      .collect::<Vec<_>>();
+
+    if failures.is_empty() {
+      Ok(Ok(()))
+    } else {
+      Ok(Err(failures))
+    }
+  }
+
+  /// Partitions the constraints into two shapes over the same variables, e.g., for two provers,
+  /// such that the numbers of non-zero entries of `A`, `B`, and `C` in the two are close.
+  ///
+  /// Constraints are assigned greedily in decreasing order of their number of non-zero entries,
+  /// each to the shape with fewer entries so far, which takes `O(num_cons log num_cons)` time and
+  /// leaves the two totals within the largest per-constraint count of each other. Each shape keeps
+  /// its constraints in their original relative order, and neither is padded.
+  pub fn density_balanced_split(&self) -> (R1CSShape<E>, R1CSShape<E>) {
+    let nnz = |M: &SparseMatrix<E::Scalar>, i: usize| M.indptr[i + 1] - M.indptr[i];
+    let mut order = (0..self.num_cons).collect::<Vec<_>>();
+    order.sort_by_key(|&i| std::cmp::Reverse(nnz(&self.A, i) + nnz(&self.B, i) + nnz(&self.C, i)));
+
+    let (mut rows, mut totals) = ([vec![], vec![]], [0, 0]);
+    for i in order {
+      let bin = if totals[0] <= totals[1] { 0 } else { 1 };
+      totals[bin] += nnz(&self.A, i) + nnz(&self.B, i) + nnz(&self.C, i);
+      rows[bin].push(i);
+    }
+
+    let [first, second] = rows.map(|mut rows| {
+      rows.sort_unstable();
+      R1CSShape {
+        num_cons: rows.len(),
+        num_vars: self.num_vars,
+        num_io: self.num_io,
+        A: self.A.select_rows(&rows),
+        B: self.B.select_rows(&rows),
+        C: self.C.select_rows(&rows),
+        digest: OnceCell::new(),
+      }
+    });
+    (first, second)
+  }
+
+  /// Returns `A`, `B`, and `C` as dense `num_cons x (num_vars + 1 + num_io)` matrices, row by row,
+  /// with columns in the order of `z = (W, 1, X)`. Intended for debugging and for exporting a
+  /// circuit to linear algebra tools.
+  ///
+  /// Returns `NovaError::TooLargeForDenseForm` if the three matrices together would have more than
+  /// `max_entries` entries.
+  pub fn to_evaluation_form(
+    &self,
+    max_entries: usize,
+  ) -> Result<
+    (
+      DenseMatrix<E::Scalar>,
+      DenseMatrix<E::Scalar>,
+      DenseMatrix<E::Scalar>,
+    ),
+    NovaError,
+  > {
+    let cols = self.num_vars + 1 + self.num_io;
+    let size = self.num_cons.saturating_mul(cols).saturating_mul(3);
+    if size > max_entries {
+      return Err(NovaError::TooLargeForDenseForm { size });
+    }
+
+    let to_dense = |M: &SparseMatrix<E::Scalar>| {
+      (0..self.num_cons)
+        .map(|i| {
+          let mut row = vec![E::Scalar::ZERO; cols];
+          for j in M.indptr[i]..M.indptr[i + 1] {
+            row[M.indices[j]] += M.data[j];
+          }
+          row
+        })
+        .collect::<Vec<_>>()
+    };
+    Ok((to_dense(&self.A), to_dense(&self.B), to_dense(&self.C)))
+  }
+
+  /// Returns the index of the first constraint that no `z = (W, 1, X)` can satisfy, if any.
+  ///
+  /// Such a constraint has `Az[i] * Bz[i] - Cz[i]` equal to a non-zero constant as a polynomial in `z`.
+  /// This can only happen when `A[i]` or `B[i]` is constant, since the product of two non-constant
+  /// linear forms is quadratic, so each row is checked with a single pass over its entries.
+  pub fn detect_unsatisfiable_constraints(&self) -> Option<usize> {
+    // the column of z that holds the constant 1
+    let one = self.num_vars;
+
+    // the non-zero entries of row i of M, sorted by column
+    let row = |M: &SparseMatrix<E::Scalar>, i: usize| {
+      M.get_row_unchecked(&[M.indptr[i], M.indptr[i + 1]])
+        .filter(|(val, _)| !bool::from(val.is_zero()))
+        .map(|(val, col)| (*col, *val))
+        .collect::<Vec<_>>()
+    };
+
+    // the value of a linear form if it is a constant
+    let constant = |l: &[(usize, E::Scalar)]| match l {
+      [] => Some(E::Scalar::ZERO),
+      [(col, val)] if *col == one => Some(*val),
+      _ => None,
+    };
+
+    (0..self.num_cons).find(|&i| {
+      let (a, b, c) = (row(&self.A, i), row(&self.B, i), row(&self.C, i));
+
+      // Az[i] * Bz[i] as a linear form, if it is one
+      let (k, l) = match (constant(&a), constant(&b)) {
+        (Some(k), _) => (k, b),
+        (None, Some(k)) => (k, a),
+        (None, None) => return false,
+      };
+
+      // Az[i] * Bz[i] - Cz[i]
+      let mut diff = BTreeMap::new();
+      for (col, val) in l {
+        *diff.entry(col).or_insert(E::Scalar::ZERO) += k * val;
+      }
+      for (col, val) in c {
+        *diff.entry(col).or_insert(E::Scalar::ZERO) -= val;
+      }
+      diff.retain(|_, val| !bool::from(val.is_zero()));
+
+      diff.len() == 1 && diff.contains_key(&one)
+    })
+  }
+
   /// A method to compute a commitment to the cross-term `T` given a
   /// Relaxed R1CS instance-witness pair and an R1CS instance-witness pair
   pub fn commit_T(
@@ -286,7 +500,7 @@ impl<E: Engine> R1CSShape<E> {
       .map(|(((az, bz), cz), e)| *az * *bz - u * *cz - *e)
       .collect::<Vec<E::Scalar>>();
 
-    let comm_T = CE::<E>::commit(ck, &T, r_T);
+    let comm_T = CE::<E>::try_commit(ck, &T, r_T)?;
 
     Ok((T, comm_T))
   }
@@ -325,7 +539,7 @@ impl<E: Engine> R1CSShape<E> {
       .map(|((((az, bz), cz), e1), e2)| *az * *bz - u * *cz - *e1 - *e2)
       .collect::<Vec<E::Scalar>>();
 
-    let comm_T = CE::<E>::commit(ck, &T, r_T);
+    let comm_T = CE::<E>::try_commit(ck, &T, r_T)?;
 
     Ok((T, comm_T))
   }
@@ -419,14 +633,14 @@ impl<E: Engine> R1CSShape<E> {
 
     // compute commitments to W,E in parallel
     let (comm_W, comm_E) = rayon::join(
-      || CE::<E>::commit(ck, &Z[..self.num_vars], &r_W),
-      || CE::<E>::commit(ck, &E, &r_E),
+      || CE::<E>::try_commit(ck, &Z[..self.num_vars], &r_W),
+      || CE::<E>::try_commit(ck, &E, &r_E),
     );
 
     Ok((
       RelaxedR1CSInstance {
-        comm_W,
-        comm_E,
+        comm_W: comm_W?,
+        comm_E: comm_E?,
         u,
         X: Z[self.num_vars + 1..].to_vec(),
       },
@@ -452,6 +666,125 @@ impl<E: Engine> R1CSWitness<E> {
     })
   }
 
+  /// Returns the witness as a slice of field elements.
+  ///
+  /// The slice holds exactly the `num_vars` private variables of the shape and excludes the blind `r_W`.
+  /// The full assignment multiplied by the R1CS matrices is `z = (W, 1, X)`, where the constant `1`
+  /// and the `num_io` public inputs `X` come from the corresponding `R1CSInstance`.
+  pub fn to_field_elements(&self) -> &[E::Scalar] {
+    &self.W
+  }
+
+  /// Creates a witness from field elements in the layout returned by `to_field_elements`,
+  /// i.e., `v` must hold exactly `S.num_vars` private variables. A fresh blind is sampled.
+  ///
+  /// Returns `NovaError::InvalidWitnessLength` if `v` does not hold `S.num_vars` elements.
+  pub fn from_field_elements(
+    S: &R1CSShape<E>,
+    v: Vec<E::Scalar>,
+  ) -> Result<R1CSWitness<E>, NovaError> {
+    if v.len() != S.num_vars {
+      return Err(NovaError::InvalidWitnessLength);
+    }
+
+    Ok(R1CSWitness {
+      W: v,
+      r_W: E::Scalar::random(&mut OsRng),
+    })
+  }
+
+  /// Reads a witness produced by an external tool, in the following binary format:
+  ///
+  /// - a header of 4 bytes holding the number of field elements `n` as a little-endian `u32`,
+  ///   which must equal `S.num_vars`;
+  /// - `n` field elements, in the layout of `to_field_elements`, each encoded as its canonical
+  ///   little-endian representation in exactly 32 bytes (the length of `PrimeField::Repr` for
+  ///   every engine in this crate), and less than the field modulus.
+  ///
+  /// Nothing may follow the last element. The witness gets a fresh blind, as in `from_field_elements`.
+  ///
+  /// Returns `NovaError::InvalidWitnessLength` if the header does not match `S.num_vars`, and
+  /// `NovaError::SerializationError` if `bytes` is truncated, too long, or holds a non-canonical element.
+  pub fn from_binary_format(S: &R1CSShape<E>, bytes: &[u8]) -> Result<R1CSWitness<E>, NovaError> {
+    let err = |reason: &str| NovaError::SerializationError {
+      reason: reason.to_string(),
+    };
+
+    let (header, body) = bytes
+      .split_first_chunk::<4>()
+      .ok_or_else(|| err("missing length header"))?;
+    if u32::from_le_bytes(*header) as usize != S.num_vars {
+      return Err(NovaError::InvalidWitnessLength);
+    }
+
+    let mut repr = <E::Scalar as PrimeField>::Repr::default();
+    let width = repr.as_ref().len();
+    if body.len() != S.num_vars * width {
+      return Err(err("body length does not match the header"));
+    }
+
+    let W = body
+      .chunks_exact(width)
+      .map(|chunk| {
+        repr.as_mut().copy_from_slice(chunk);
+        Option::from(E::Scalar::from_repr(repr)).ok_or_else(|| err("non-canonical field element"))
+      })
+      .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(R1CSWitness {
+      W,
+      r_W: E::Scalar::random(&mut OsRng),
+    })
+  }
+
+  /// Returns the witness `w1 + coeff * w2`, combining the blinds the same way,
+  /// so that its commitment is `comm(w1) + coeff * comm(w2)`
+  pub fn linear_combination(
+    w1: &R1CSWitness<E>,
+    w2: &R1CSWitness<E>,
+    coeff: &E::Scalar,
+  ) -> Result<R1CSWitness<E>, NovaError> {
+    if w1.W.len() != w2.W.len() {
+      return Err(NovaError::InvalidWitnessLength);
+    }
+
+    let W = w1
+      .W
+      .par_iter()
+      .zip(w2.W.par_iter())
+      .map(|(a, b)| *a + *coeff * *b)
+      .collect::<Vec<_>>();
+    let r_W = w1.r_W + *coeff * w2.r_W;
+
+    Ok(R1CSWitness { W, r_W })
+  }
+
+  /// Checks that the witness satisfies the constraints of `S` with the public inputs of `U`,
+  /// i.e., that `Az * Bz = Cz` for `z = (W, 1, U.X)`.
+  ///
+  /// Unlike `R1CSShape::is_sat`, this does not need a commitment key and does not check `U.comm_W`.
+  pub fn check_satisfies_shape(
+    &self,
+    U: &R1CSInstance<E>,
+    S: &R1CSShape<E>,
+  ) -> Result<(), NovaError> {
+    if self.W.len() != S.num_vars {
+      return Err(NovaError::InvalidWitnessLength);
+    }
+    if U.X.len() != S.num_io {
+      return Err(NovaError::InvalidInputLength);
+    }
+
+    let z = [self.W.clone(), vec![E::Scalar::ONE], U.X.clone()].concat();
+    S.hadamard_check(&z)?.map_err(|failures| NovaError::UnSat {
+      reason: format!(
+        "{} constraints are unsatisfied, the first at index {}",
+        failures.len(),
+        failures[0].0
+      ),
+    })
+  }
+
   /// Commits to the witness using the supplied generators
   pub fn commit(&self, ck: &CommitmentKey<E>) -> Commitment<E> {
     CE::<E>::commit(ck, &self.W, &self.r_W)
@@ -878,4 +1211,385 @@ mod tests {
     test_random_sample_with::<Bn256EngineKZG>();
     test_random_sample_with::<Secp256k1Engine>();
   }
+
+  fn test_witness_field_elements_with<E: Engine>() {
+    let S = tiny_r1cs::<E>(4);
+    let W = (0..S.num_vars)
+      .map(|i| E::Scalar::from(i as u64))
+      .collect::<Vec<_>>();
+
+    let w = R1CSWitness::<E>::new(&S, &W).unwrap();
+    assert_eq!(w.to_field_elements(), W.as_slice());
+
+    let w2 = R1CSWitness::<E>::from_field_elements(&S, w.to_field_elements().to_vec()).unwrap();
+    assert_eq!(w2.to_field_elements(), w.to_field_elements());
+
+    assert_eq!(
+      R1CSWitness::<E>::from_field_elements(&S, W[1..].to_vec()).err(),
+      Some(NovaError::InvalidWitnessLength)
+    );
+  }
+
+  #[test]
+  fn test_witness_field_elements() {
+    test_witness_field_elements_with::<PallasEngine>();
+    test_witness_field_elements_with::<Bn256EngineKZG>();
+    test_witness_field_elements_with::<Secp256k1Engine>();
+  }
+
+  fn test_witness_linear_combination_with<E: Engine>() {
+    let S = tiny_r1cs::<E>(4);
+    let ck = S.commitment_key(&*default_ck_hint());
+    let random_witness = || {
+      let W = (0..S.num_vars)
+        .map(|_| E::Scalar::random(&mut OsRng))
+        .collect::<Vec<_>>();
+      R1CSWitness::<E>::new(&S, &W).unwrap()
+    };
+    let (w1, w2) = (random_witness(), random_witness());
+    let coeff = E::Scalar::random(&mut OsRng);
+
+    let w = R1CSWitness::linear_combination(&w1, &w2, &coeff).unwrap();
+    for i in 0..S.num_vars {
+      assert_eq!(w.W[i], w1.W[i] + coeff * w2.W[i]);
+    }
+    assert_eq!(w.commit(&ck), w1.commit(&ck) + w2.commit(&ck) * coeff);
+
+    let w3 = R1CSWitness::<E> {
+      W: w2.W[1..].to_vec(),
+      r_W: w2.r_W,
+    };
+    assert_eq!(
+      R1CSWitness::linear_combination(&w1, &w3, &coeff),
+      Err(NovaError::InvalidWitnessLength)
+    );
+  }
+
+  #[test]
+  fn test_witness_linear_combination() {
+    test_witness_linear_combination_with::<PallasEngine>();
+    test_witness_linear_combination_with::<Bn256EngineKZG>();
+    test_witness_linear_combination_with::<Secp256k1Engine>();
+  }
+
+  fn test_hadamard_check_with<E: Engine>() {
+    let S = tiny_r1cs::<E>(4);
+    let f = |v: u64| E::Scalar::from(v);
+
+    // x = 2, y = 15: z = (Z0, Z1, Z2, 0, 1, I0, I1)
+    let mut z = vec![f(4), f(8), f(10), f(0), f(1), f(2), f(15)];
+    assert_eq!(S.hadamard_check(&z), Ok(Ok(())));
+
+    // corrupting Z0 breaks the first two constraints, and corrupting the output breaks the last
+    z[0] = f(5);
+    z[6] = f(16);
+    assert_eq!(
+      S.hadamard_check(&z),
+      Ok(Err(vec![
+        (0, f(2), f(2), f(5)),
+        (1, f(5), f(2), f(8)),
+        (3, f(15), f(1), f(16)),
+      ]))
+    );
+
+    // an assignment of the wrong length is an error, not a panic
+    assert_eq!(
+      S.hadamard_check(&z[1..]),
+      Err(NovaError::InvalidWitnessLength)
+    );
+  }
+
+  fn test_check_satisfies_shape_with<E: Engine>() {
+    let S = tiny_r1cs::<E>(4);
+    let f = |v: u64| E::Scalar::from(v);
+    let U = R1CSInstance::<E> {
+      comm_W: Commitment::<E>::default(),
+      X: vec![f(2), f(15)],
+    };
+
+    let W = R1CSWitness::<E>::new(&S, &[f(4), f(8), f(10)]).unwrap();
+    assert_eq!(W.check_satisfies_shape(&U, &S), Ok(()));
+
+    // the public inputs are taken from the instance
+    let U_bad = R1CSInstance::<E> {
+      comm_W: U.comm_W,
+      X: vec![f(2), f(16)],
+    };
+    assert!(matches!(
+      W.check_satisfies_shape(&U_bad, &S),
+      Err(NovaError::UnSat { .. })
+    ));
+    let U_short = R1CSInstance::<E> {
+      comm_W: U.comm_W,
+      X: vec![f(2)],
+    };
+    assert_eq!(
+      W.check_satisfies_shape(&U_short, &S),
+      Err(NovaError::InvalidInputLength)
+    );
+  }
+
+  fn test_check_public_io_consistency_with<E: Engine>() {
+    let S = tiny_r1cs::<E>(4);
+    let f = |v: u64| E::Scalar::from(v);
+    let U = R1CSInstance::<E> {
+      comm_W: Commitment::<E>::default(),
+      X: vec![f(2), f(15)],
+    };
+    let W = R1CSWitness::<E>::new(&S, &[f(4), f(8), f(10)]).unwrap();
+    assert_eq!(S.check_public_io_consistency(&U, &W), Ok(()));
+
+    // a witness with extra entries, e.g., public inputs allocated as private variables
+    let W_io = R1CSWitness::<E> {
+      W: [W.W.clone(), U.X.clone()].concat(),
+      r_W: W.r_W,
+    };
+    assert_eq!(
+      S.check_public_io_consistency(&U, &W_io),
+      Err(NovaError::InvalidWitnessLength)
+    );
+    let U_short = R1CSInstance::<E> {
+      comm_W: U.comm_W,
+      X: vec![f(2)],
+    };
+    assert_eq!(
+      S.check_public_io_consistency(&U_short, &W),
+      Err(NovaError::InvalidInputLength)
+    );
+  }
+
+  fn test_from_binary_format_with<E: Engine>() {
+    let S = tiny_r1cs::<E>(4);
+    let f = |v: u64| E::Scalar::from(v);
+    let W = [f(4), f(8), f(10), -f(1)];
+    let encode = |header: u32, W: &[E::Scalar]| {
+      let mut bytes = header.to_le_bytes().to_vec();
+      W.iter()
+        .for_each(|w| bytes.extend_from_slice(w.to_repr().as_ref()));
+      bytes
+    };
+
+    let bytes = encode(4, &W);
+    assert_eq!(bytes.len(), 4 + 4 * 32);
+    assert_eq!(bytes[4], 4);
+    let witness = R1CSWitness::<E>::from_binary_format(&S, &bytes).unwrap();
+    assert_eq!(witness.to_field_elements(), &W[..]);
+
+    assert_eq!(
+      R1CSWitness::<E>::from_binary_format(&S, &encode(3, &W[..3])),
+      Err(NovaError::InvalidWitnessLength)
+    );
+    for bad in [
+      &bytes[..3],
+      &bytes[..bytes.len() - 1],
+      &[&bytes[..], &[0]].concat(),
+    ] {
+      assert!(matches!(
+        R1CSWitness::<E>::from_binary_format(&S, bad),
+        Err(NovaError::SerializationError { .. })
+      ));
+    }
+
+    // an element at or above the modulus is rejected
+    let mut bytes = bytes;
+    bytes[4..36].fill(0xff);
+    assert!(matches!(
+      R1CSWitness::<E>::from_binary_format(&S, &bytes),
+      Err(NovaError::SerializationError { .. })
+    ));
+  }
+
+  #[test]
+  fn test_from_binary_format() {
+    test_from_binary_format_with::<PallasEngine>();
+    test_from_binary_format_with::<Bn256EngineKZG>();
+    test_from_binary_format_with::<Secp256k1Engine>();
+  }
+
+  #[test]
+  fn test_check_public_io_consistency() {
+    test_check_public_io_consistency_with::<PallasEngine>();
+    test_check_public_io_consistency_with::<Bn256EngineKZG>();
+    test_check_public_io_consistency_with::<Secp256k1Engine>();
+  }
+
+  #[test]
+  fn test_check_satisfies_shape() {
+    test_check_satisfies_shape_with::<PallasEngine>();
+    test_check_satisfies_shape_with::<Bn256EngineKZG>();
+    test_check_satisfies_shape_with::<Secp256k1Engine>();
+  }
+
+  #[test]
+  fn test_hadamard_check() {
+    test_hadamard_check_with::<PallasEngine>();
+    test_hadamard_check_with::<Bn256EngineKZG>();
+    test_hadamard_check_with::<Secp256k1Engine>();
+  }
+
+  fn test_density_balanced_split_with<E: Engine>() {
+    let S = tiny_r1cs::<E>(4);
+    let (S1, S2) = S.density_balanced_split();
+
+    // constraints 0 and 1 have three entries each, 2 and 3 have four
+    let nnz = |S: &R1CSShape<E>| S.A.len() + S.B.len() + S.C.len();
+    assert_eq!((S1.num_cons, S2.num_cons), (2, 2));
+    assert_eq!((nnz(&S1), nnz(&S2)), (7, 7));
+    assert_eq!(S1.A, S.A.select_rows(&[0, 2]));
+    assert_eq!(S2.C, S.C.select_rows(&[1, 3]));
+
+    // a satisfying assignment satisfies both halves
+    let f = |v: u64| E::Scalar::from(v);
+    let z = [f(4), f(8), f(10), f(0), f(1), f(2), f(15)];
+    assert_eq!(S1.hadamard_check(&z), Ok(Ok(())));
+    assert_eq!(S2.hadamard_check(&z), Ok(Ok(())));
+    assert_eq!((S1.num_vars, S1.num_io), (S.num_vars, S.num_io));
+  }
+
+  #[test]
+  fn test_density_balanced_split() {
+    test_density_balanced_split_with::<PallasEngine>();
+    test_density_balanced_split_with::<Bn256EngineKZG>();
+    test_density_balanced_split_with::<Secp256k1Engine>();
+  }
+
+  fn test_to_evaluation_form_with<E: Engine>() {
+    let S = tiny_r1cs::<E>(4);
+    let (A, B, C) = S.to_evaluation_form(usize::MAX).unwrap();
+    for M in [&A, &B, &C] {
+      assert_eq!(M.len(), S.num_cons);
+      assert!(M.iter().all(|row| row.len() == S.num_vars + 1 + S.num_io));
+    }
+
+    // multiplying the dense matrices by z agrees with the sparse product
+    let z = [4, 8, 10, 0, 1, 2, 15].map(|v| E::Scalar::from(v as u64));
+    let dense_mul = |M: &DenseMatrix<E::Scalar>| {
+      M.iter()
+        .map(|row| row.iter().zip(z.iter()).map(|(m, z)| *m * z).sum())
+        .collect::<Vec<E::Scalar>>()
+    };
+    let (Az, Bz, Cz) = S.multiply_vec(&z).unwrap();
+    assert_eq!((dense_mul(&A), dense_mul(&B), dense_mul(&C)), (Az, Bz, Cz));
+
+    // the limit counts the entries of all three matrices
+    let size = 3 * S.num_cons * (S.num_vars + 1 + S.num_io);
+    assert!(S.to_evaluation_form(size).is_ok());
+    assert!(matches!(
+      S.to_evaluation_form(size - 1),
+      Err(NovaError::TooLargeForDenseForm { size: s }) if s == size
+    ));
+  }
+
+  #[test]
+  fn test_to_evaluation_form() {
+    test_to_evaluation_form_with::<PallasEngine>();
+    test_to_evaluation_form_with::<Bn256EngineKZG>();
+    test_to_evaluation_form_with::<Secp256k1Engine>();
+  }
+
+  fn test_detect_unsatisfiable_constraints_with<E: Engine>() {
+    assert_eq!(tiny_r1cs::<E>(4).detect_unsatisfiable_constraints(), None);
+
+    // z = (x0, x1, 1)
+    let f = |v: u64| E::Scalar::from(v);
+    let shape = |A: &[(usize, usize, E::Scalar)],
+                 B: &[(usize, usize, E::Scalar)],
+                 C: &[(usize, usize, E::Scalar)]| {
+      R1CSShape::<E>::new(
+        4,
+        2,
+        0,
+        SparseMatrix::new(A, 4, 3),
+        SparseMatrix::new(B, 4, 3),
+        SparseMatrix::new(C, 4, 3),
+      )
+      .unwrap()
+    };
+
+    // row 0: x0 * x1 = 0, row 1: empty, row 2: 2 * x0 = 2 * x0 + 1, row 3: 2 * 3 = 5
+    let S = shape(
+      &[(0, 0, f(1)), (2, 2, f(2)), (3, 2, f(2))],
+      &[(0, 1, f(1)), (2, 0, f(1)), (3, 2, f(3))],
+      &[(2, 0, f(2)), (2, 2, f(1)), (3, 2, f(5))],
+    );
+    assert_eq!(S.detect_unsatisfiable_constraints(), Some(2));
+
+    // row 2: 2 * x0 = 2 * x0 is always satisfied, row 3: 2 * 3 = 6 as well
+    let S = shape(
+      &[(0, 0, f(1)), (2, 2, f(2)), (3, 2, f(2))],
+      &[(0, 1, f(1)), (2, 0, f(1)), (3, 2, f(3))],
+      &[(2, 0, f(2)), (3, 2, f(6))],
+    );
+    assert_eq!(S.detect_unsatisfiable_constraints(), None);
+
+    // row 1: 0 = 1
+    let S = shape(
+      &[(0, 0, f(1)), (2, 2, f(2)), (3, 2, f(2))],
+      &[(0, 1, f(1)), (2, 0, f(1)), (3, 2, f(3))],
+      &[(1, 2, f(1)), (2, 0, f(2)), (3, 2, f(6))],
+    );
+    assert_eq!(S.detect_unsatisfiable_constraints(), Some(1));
+  }
+
+  #[test]
+  fn test_detect_unsatisfiable_constraints() {
+    test_detect_unsatisfiable_constraints_with::<PallasEngine>();
+    test_detect_unsatisfiable_constraints_with::<Bn256EngineKZG>();
+    test_detect_unsatisfiable_constraints_with::<Secp256k1Engine>();
+  }
+
+  fn test_from_sparse_matrices_with<E: Engine>() {
+    let f = |v: u64| E::Scalar::from(v);
+
+    // x0 * x1 = x2 and (x0 + 5) * 1 = x1 over z = (x0, x1, x2, x3, 1)
+    let A = [(1, 4, f(5)), (0, 0, f(1)), (1, 0, f(1))];
+    let B = [(0, 1, f(1)), (1, 4, f(1))];
+    let C = [(0, 2, f(1)), (1, 1, f(1))];
+
+    // entries may come in any order
+    let S = R1CSShape::<E>::from_sparse_matrices(2, 4, 0, &A, &B, &C).unwrap();
+    let mut A_sorted = A.to_vec();
+    A_sorted.sort_by_key(|(row, col, _)| (*row, *col));
+    assert_eq!(S.A, SparseMatrix::new(&A_sorted, 2, 5));
+    assert_eq!(
+      S.hadamard_check(&[f(2), f(7), f(14), f(0), f(1)]),
+      Ok(Ok(()))
+    );
+
+    // out-of-bounds and duplicate entries are rejected
+    assert_eq!(
+      R1CSShape::<E>::from_sparse_matrices(2, 4, 0, &[(2, 0, f(1))], &B, &C),
+      Err(NovaError::InvalidIndex)
+    );
+    assert_eq!(
+      R1CSShape::<E>::from_sparse_matrices(2, 4, 0, &A, &[(0, 5, f(1))], &C),
+      Err(NovaError::InvalidIndex)
+    );
+    assert_eq!(
+      R1CSShape::<E>::from_sparse_matrices(2, 4, 0, &A, &B, &[(0, 2, f(1)), (0, 2, f(1))]),
+      Err(NovaError::InvalidIndex)
+    );
+  }
+
+  #[test]
+  fn test_validate() {
+    type E = PallasEngine;
+
+    assert_eq!(tiny_r1cs::<E>(4).validate(), Ok(()));
+
+    // shapes that bypass `R1CSShape::new`, e.g., through deserialization, are checked as well
+    let mut S = tiny_r1cs::<E>(4);
+    S.num_io = 1;
+    assert_eq!(S.validate(), Err(NovaError::InvalidIndex));
+    let bytes = bincode::serialize(&S).unwrap();
+    let S: R1CSShape<E> = bincode::deserialize(&bytes).unwrap();
+    assert_eq!(S.validate(), Err(NovaError::InvalidIndex));
+  }
+
+  #[test]
+  fn test_from_sparse_matrices() {
+    test_from_sparse_matrices_with::<PallasEngine>();
+    test_from_sparse_matrices_with::<Bn256EngineKZG>();
+    test_from_sparse_matrices_with::<Secp256k1Engine>();
+  }
 }
@@ -96,6 +96,26 @@ impl<F: PrimeField> SparseMatrix<F> {
       .collect()
   }
 
+  /// Returns the matrix formed by the given rows, in the given order
+  pub fn select_rows(&self, rows: &[usize]) -> Self {
+    let mut indptr = Vec::with_capacity(rows.len() + 1);
+    indptr.push(0);
+    let (mut indices, mut data) = (vec![], vec![]);
+    for &row in rows {
+      let (start, end) = (self.indptr[row], self.indptr[row + 1]);
+      indices.extend_from_slice(&self.indices[start..end]);
+      data.extend_from_slice(&self.data[start..end]);
+      indptr.push(indices.len());
+    }
+
+    SparseMatrix {
+      data,
+      indices,
+      indptr,
+      cols: self.cols,
+    }
+  }
+
   /// number of non-zero entries
   pub fn len(&self) -> usize {
     *self.indptr.last().unwrap()
@@ -232,6 +252,32 @@ mod tests {
     assert_eq!(result, vec![Fr::from(25), Fr::from(9), Fr::from(4)]);
   }
 
+  #[test]
+  fn test_select_rows() {
+    let matrix_data = vec![
+      (0, 1, Fr::from(2)),
+      (0, 2, Fr::from(7)),
+      (1, 2, Fr::from(3)),
+      (2, 0, Fr::from(4)),
+    ];
+    let sparse_matrix = SparseMatrix::<Fr>::new(&matrix_data, 3, 3);
+
+    let selected = sparse_matrix.select_rows(&[2, 0]);
+    assert_eq!(
+      selected,
+      SparseMatrix::<Fr>::new(
+        &[
+          (0, 0, Fr::from(4)),
+          (1, 1, Fr::from(2)),
+          (1, 2, Fr::from(7))
+        ],
+        2,
+        3
+      )
+    );
+    assert!(sparse_matrix.select_rows(&[]).is_empty());
+  }
+
   fn coo_strategy() -> BoxedStrategy<Vec<(usize, usize, FWrap<Fr>)>> {
     let coo_strategy = any::<FWrap<Fr>>().prop_flat_map(|f| (0usize..100, 0usize..100, Just(f)));
     proptest::collection::vec(coo_strategy, 10).boxed()
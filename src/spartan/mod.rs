@@ -15,6 +15,12 @@ pub(crate) mod math;
 pub(crate) mod polys;
 pub(crate) mod sumcheck;
 
+pub use polys::{
+  eq::{EqPolynomial, SparseEqPolynomial},
+  power::PowPolynomial,
+  univariate::UniPoly,
+};
+
 use crate::{
   r1cs::{R1CSShape, SparseMatrix},
   traits::Engine,
@@ -1,5 +1,7 @@
 //! `EqPolynomial`: Represents multilinear extension of equality polynomials, evaluated based on binary input values.
+use crate::errors::NovaError;
 use ff::PrimeField;
+use rand_core::RngCore;
 use rayon::prelude::*;
 
 /// Represents the multilinear extension polynomial (MLE) of the equality polynomial $eq(x,e)$, denoted as $\tilde{eq}(x, e)$.
@@ -26,6 +28,73 @@ impl<Scalar: PrimeField> EqPolynomial<Scalar> {
     EqPolynomial { r }
   }
 
+  /// Creates the `EqPolynomial` that binds the claims left after a sum-check to the challenges
+  /// `challenges` of its rounds, in the order in which they were sampled.
+  ///
+  /// This is `EqPolynomial::new(challenges)`: since the sum-check binds the variables starting
+  /// with the top one, `evals()[i]` is the weight of the `i`-th entry of the polynomial being summed.
+  pub const fn from_sumcheck_challenges(challenges: Vec<Scalar>) -> Self {
+    Self::new(challenges)
+  }
+
+  /// Fixes the variable `x_{var_index}` to `t`, e.g., in a sum-check round that binds the
+  /// variables in a custom order.
+  ///
+  /// The result is not an `EqPolynomial` by itself: since $\tilde{eq}$ is a product of one factor per
+  /// variable, it is the constant `r_k * t + (1 - r_k) * (1 - t)` times the `EqPolynomial` over the
+  /// remaining variables, and both are returned. For `var_index = 0` this is the same as binding
+  /// the top variable of `MultilinearPolynomial::new(self.evals())` to `t`.
+  ///
+  /// Panics if `var_index` is not less than the number of variables.
+  pub fn split_at_variable(&self, var_index: usize, t: Scalar) -> (Scalar, EqPolynomial<Scalar>) {
+    let r_k = self.r[var_index];
+    let factor = r_k * t + (Scalar::ONE - r_k) * (Scalar::ONE - t);
+    let r = [&self.r[..var_index], &self.r[var_index + 1..]].concat();
+    (factor, EqPolynomial::new(r))
+  }
+
+  /// Creates a new `EqPolynomial` from the affine image `A·r + b` of the challenge vector `r`.
+  ///
+  /// The matrix `A` is given row by row; it must have one row per entry of `b`,
+  /// and every row must have one entry per entry of `r`.
+  ///
+  /// Returns `NovaError::InvalidInputLength` if the dimensions are inconsistent.
+  pub fn compose_with_affine_map(
+    r: &[Scalar],
+    A: &[Vec<Scalar>],
+    b: &[Scalar],
+  ) -> Result<Self, NovaError> {
+    if A.len() != b.len() || A.iter().any(|row| row.len() != r.len()) {
+      return Err(NovaError::InvalidInputLength);
+    }
+
+    let r_prime = A
+      .iter()
+      .zip(b.iter())
+      .map(|(row, b_i)| {
+        row
+          .iter()
+          .zip(r.iter())
+          .fold(*b_i, |acc, (a_ij, r_j)| acc + *a_ij * r_j)
+      })
+      .collect::<Vec<_>>();
+
+    Ok(EqPolynomial::new(r_prime))
+  }
+
+  /// Samples a uniformly random point of the Boolean hypercube `{0, 1}^n`, e.g., for benchmarking `evaluate`
+  pub fn sample_boolean_hypercube_point(n: usize, rng: &mut impl RngCore) -> Vec<Scalar> {
+    (0..n)
+      .map(|_| {
+        if rng.next_u32() & 1 == 1 {
+          Scalar::ONE
+        } else {
+          Scalar::ZERO
+        }
+      })
+      .collect()
+  }
+
   /// Evaluates the `EqPolynomial` at a given point `rx`.
   ///
   /// This function computes the value of the polynomial at the point specified by `rx`.
@@ -70,6 +139,70 @@ impl<Scalar: PrimeField> EqPolynomial<Scalar> {
 
     evals
   }
+
+  /// Encodes the evaluations of the `EqPolynomial` over the boolean hypercube as a Reed-Solomon codeword
+  /// over `domain`.
+  ///
+  /// The evaluations are treated as the coefficients of a univariate polynomial of degree `2^|r| - 1`,
+  /// with the evaluation at index `i` being the coefficient of `X^i`. Its value at a point `d` is the
+  /// multilinear extension evaluated at `(d^{2^{|r|-1}}, ..., d^2, d)`, which factors as
+  /// $\prod_i (1 - r_i + r_i * d^{2^{|r|-1-i}})$ and so never materializes the `2^|r|` evaluations.
+  pub fn to_reed_solomon(&self, domain: &[Scalar]) -> Vec<Scalar> {
+    domain
+      .par_iter()
+      .map(|d| {
+        let mut d_pow = *d;
+        let mut acc = Scalar::ONE;
+        for r_i in self.r.iter().rev() {
+          acc *= Scalar::ONE - r_i + *r_i * d_pow;
+          d_pow = d_pow.square();
+        }
+        acc
+      })
+      .collect()
+  }
+
+  /// Splits the coordinates of `r` into the zero ones, whose factor is just `1 - x_i`,
+  /// and the remaining ones, e.g., when most challenges are fixed to zero.
+  pub fn to_sparse_representation(&self) -> SparseEqPolynomial<Scalar> {
+    let (zeros, nonzeros): (Vec<_>, Vec<_>) = self
+      .r
+      .iter()
+      .enumerate()
+      .partition(|(_, r_i)| bool::from(r_i.is_zero()));
+
+    SparseEqPolynomial {
+      num_vars: self.r.len(),
+      zeros: zeros.into_iter().map(|(i, _)| i).collect(),
+      nonzeros: nonzeros.into_iter().map(|(i, r_i)| (i, *r_i)).collect(),
+    }
+  }
+}
+
+/// An `EqPolynomial` whose zero challenge coordinates are stored by index only,
+/// as produced by `EqPolynomial::to_sparse_representation`
+#[derive(Debug)]
+pub struct SparseEqPolynomial<Scalar: PrimeField> {
+  num_vars: usize,
+  zeros: Vec<usize>,
+  nonzeros: Vec<(usize, Scalar)>,
+}
+
+impl<Scalar: PrimeField> SparseEqPolynomial<Scalar> {
+  /// Evaluates the polynomial at `point`, which takes a single subtraction and multiplication
+  /// per zero challenge coordinate. The result matches `EqPolynomial::evaluate`.
+  ///
+  /// Panics if `point` does not have one entry per variable.
+  pub fn evaluate(&self, point: &[Scalar]) -> Scalar {
+    assert_eq!(self.num_vars, point.len());
+    let zeros = self
+      .zeros
+      .iter()
+      .fold(Scalar::ONE, |acc, i| acc * (Scalar::ONE - point[*i]));
+    self.nonzeros.iter().fold(zeros, |acc, (i, r_i)| {
+      acc * (point[*i] * r_i + (Scalar::ONE - point[*i]) * (Scalar::ONE - r_i))
+    })
+  }
 }
 
 impl<Scalar: PrimeField> FromIterator<Scalar> for EqPolynomial<Scalar> {
@@ -82,7 +215,10 @@ impl<Scalar: PrimeField> FromIterator<Scalar> for EqPolynomial<Scalar> {
 #[cfg(test)]
 mod tests {
   use super::*;
-  use crate::provider::{bn256_grumpkin::bn256, pasta::pallas, secp_secq::secp256k1};
+  use crate::{
+    provider::{bn256_grumpkin::bn256, pasta::pallas, secp_secq::secp256k1},
+    spartan::polys::multilinear::MultilinearPolynomial,
+  };
 
   fn test_eq_polynomial_with<F: PrimeField>() {
     let eq_poly = EqPolynomial::<F>::new(vec![F::ONE, F::ZERO, F::ONE]);
@@ -108,4 +244,157 @@ mod tests {
     test_eq_polynomial_with::<bn256::Scalar>();
     test_eq_polynomial_with::<secp256k1::Scalar>();
   }
+
+  fn test_compose_with_affine_map_with<F: PrimeField>() {
+    let r = vec![F::from(2), F::from(3)];
+
+    // A = [[1, 1], [0, 2], [1, 0]] and b = [1, 0, 5]
+    let A = vec![
+      vec![F::ONE, F::ONE],
+      vec![F::ZERO, F::from(2)],
+      vec![F::ONE, F::ZERO],
+    ];
+    let b = vec![F::ONE, F::ZERO, F::from(5)];
+
+    let eq_poly = EqPolynomial::compose_with_affine_map(&r, &A, &b).unwrap();
+    assert_eq!(eq_poly.r, vec![F::from(6), F::from(6), F::from(7)]);
+
+    // inconsistent dimensions are rejected
+    assert!(EqPolynomial::compose_with_affine_map(&r, &A, &b[1..]).is_err());
+    assert!(EqPolynomial::compose_with_affine_map(&r[1..], &A, &b).is_err());
+  }
+
+  #[test]
+  fn test_compose_with_affine_map() {
+    test_compose_with_affine_map_with::<pallas::Scalar>();
+    test_compose_with_affine_map_with::<bn256::Scalar>();
+    test_compose_with_affine_map_with::<secp256k1::Scalar>();
+  }
+
+  fn test_to_reed_solomon_with<F: PrimeField>() {
+    let eq_poly = EqPolynomial::<F>::new(vec![F::from(2), F::from(3), F::from(5)]);
+    let evals = eq_poly.evals();
+
+    let domain = (0..8).map(|i| F::from(i as u64 + 7)).collect::<Vec<_>>();
+    let codeword = eq_poly.to_reed_solomon(&domain);
+
+    // compare against Horner evaluation of the evaluations as coefficients
+    for (d, c) in domain.iter().zip(codeword.iter()) {
+      let expected = evals.iter().rev().fold(F::ZERO, |acc, e| acc * d + e);
+      assert_eq!(*c, expected);
+    }
+
+    // at X = 1 the codeword is the sum of the evaluations over the hypercube, which is one
+    assert_eq!(eq_poly.to_reed_solomon(&[F::ONE]), vec![F::ONE]);
+  }
+
+  #[test]
+  fn test_to_reed_solomon() {
+    test_to_reed_solomon_with::<pallas::Scalar>();
+    test_to_reed_solomon_with::<bn256::Scalar>();
+    test_to_reed_solomon_with::<secp256k1::Scalar>();
+  }
+
+  fn test_sample_boolean_hypercube_point_with<F: PrimeField>() {
+    let point = EqPolynomial::<F>::sample_boolean_hypercube_point(16, &mut rand::rngs::OsRng);
+    assert_eq!(point.len(), 16);
+    assert!(point.iter().all(|x| *x == F::ZERO || *x == F::ONE));
+
+    // eq(point, .) is the indicator of the sampled point
+    let eq_poly = EqPolynomial::new(point.clone());
+    assert_eq!(eq_poly.evaluate(&point), F::ONE);
+  }
+
+  #[test]
+  fn test_sample_boolean_hypercube_point() {
+    test_sample_boolean_hypercube_point_with::<pallas::Scalar>();
+    test_sample_boolean_hypercube_point_with::<bn256::Scalar>();
+    test_sample_boolean_hypercube_point_with::<secp256k1::Scalar>();
+  }
+
+  fn test_to_sparse_representation_with<F: PrimeField>() {
+    let mut rng = rand::rngs::OsRng;
+    let r = (0..8)
+      .map(|i| {
+        if i % 3 == 0 {
+          F::random(&mut rng)
+        } else {
+          F::ZERO
+        }
+      })
+      .collect::<Vec<_>>();
+    let eq_poly = EqPolynomial::new(r);
+    let sparse = eq_poly.to_sparse_representation();
+    assert_eq!(sparse.zeros, vec![1, 2, 4, 5, 7]);
+    assert_eq!(sparse.nonzeros.len(), 3);
+
+    for _ in 0..4 {
+      let point = (0..8).map(|_| F::random(&mut rng)).collect::<Vec<_>>();
+      assert_eq!(sparse.evaluate(&point), eq_poly.evaluate(&point));
+    }
+  }
+
+  #[test]
+  fn test_to_sparse_representation() {
+    test_to_sparse_representation_with::<pallas::Scalar>();
+    test_to_sparse_representation_with::<bn256::Scalar>();
+    test_to_sparse_representation_with::<secp256k1::Scalar>();
+  }
+
+  fn test_from_sumcheck_challenges_with<F: PrimeField>() {
+    let mut rng = rand::rngs::OsRng;
+    let challenges = (0..4).map(|_| F::random(&mut rng)).collect::<Vec<_>>();
+    let Z = (0..16).map(|_| F::random(&mut rng)).collect::<Vec<_>>();
+
+    // the final claim of a sum-check over Z is Z's multilinear extension at the challenges,
+    // which the eq polynomial expresses as a weighted sum of the entries of Z
+    let eq_poly = EqPolynomial::from_sumcheck_challenges(challenges.clone());
+    let weighted = eq_poly
+      .evals()
+      .iter()
+      .zip(Z.iter())
+      .fold(F::ZERO, |acc, (e, z)| acc + *e * z);
+    assert_eq!(
+      weighted,
+      MultilinearPolynomial::new(Z).evaluate(&challenges)
+    );
+  }
+
+  #[test]
+  fn test_from_sumcheck_challenges() {
+    test_from_sumcheck_challenges_with::<pallas::Scalar>();
+    test_from_sumcheck_challenges_with::<bn256::Scalar>();
+    test_from_sumcheck_challenges_with::<secp256k1::Scalar>();
+  }
+
+  fn test_split_at_variable_with<F: PrimeField>() {
+    let mut rng = rand::rngs::OsRng;
+    let eq_poly = EqPolynomial::new((0..4).map(|_| F::random(&mut rng)).collect());
+    let (x, t) = (
+      (0..4).map(|_| F::random(&mut rng)).collect::<Vec<_>>(),
+      F::random(&mut rng),
+    );
+
+    for k in 0..4 {
+      let (factor, rest) = eq_poly.split_at_variable(k, t);
+      let mut point = x.clone();
+      point[k] = t;
+      let x_rest = [&x[..k], &x[k + 1..]].concat();
+      assert_eq!(factor * rest.evaluate(&x_rest), eq_poly.evaluate(&point));
+    }
+
+    // binding the top variable matches the multilinear polynomial of the evaluations
+    let (factor, rest) = eq_poly.split_at_variable(0, t);
+    let mut poly = MultilinearPolynomial::new(eq_poly.evals());
+    poly.bind_poly_var_top(&t);
+    let expected = rest.evals().iter().map(|e| factor * e).collect::<Vec<_>>();
+    assert_eq!(poly.Z, expected);
+  }
+
+  #[test]
+  fn test_split_at_variable() {
+    test_split_at_variable_with::<pallas::Scalar>();
+    test_split_at_variable_with::<bn256::Scalar>();
+    test_split_at_variable_with::<secp256k1::Scalar>();
+  }
 }
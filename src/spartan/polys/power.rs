@@ -1,7 +1,9 @@
 //! `PowPolynomial`: Represents multilinear extension of power polynomials
 
+use crate::{errors::NovaError, spartan::math::Math};
 use core::iter::successors;
 use ff::PrimeField;
+use rand_core::{OsRng, RngCore};
 
 /// Represents the multilinear extension polynomial (MLE) of the equality polynomial $pow(x,t)$, denoted as $\tilde{pow}(x, t)$.
 ///
@@ -9,12 +11,10 @@ use ff::PrimeField;
 /// $$
 /// \tilde{power}(x, t) = \prod_{i=1}^m(1 + (t^{2^i} - 1) * x_i)
 /// $$
-#[allow(dead_code)]
 pub struct PowPolynomial<Scalar: PrimeField> {
   t_pow: Vec<Scalar>,
 }
 
-#[allow(dead_code)]
 impl<Scalar: PrimeField> PowPolynomial<Scalar> {
   /// Creates a new `PowPolynomial` from a Scalars `t`.
   pub fn new(t: &Scalar, ell: usize) -> Self {
@@ -26,16 +26,109 @@ impl<Scalar: PrimeField> PowPolynomial<Scalar> {
     PowPolynomial { t_pow }
   }
 
+  /// Creates a new `PowPolynomial` in `ell` variables from a uniformly random `t`
+  pub fn random(ell: usize, rng: &mut impl RngCore) -> Self {
+    Self::new(&Scalar::random(rng), ell)
+  }
+
+  /// Creates a new random `PowPolynomial` as in `random`, together with a random blind
+  /// for committing to its evaluations
+  pub fn random_with_blinding(ell: usize, rng: &mut impl RngCore) -> (Self, Scalar) {
+    let pow = Self::random(ell, &mut *rng);
+    (pow, Scalar::random(rng))
+  }
+
+  /// Creates a new `PowPolynomial` from `t` and its claimed evaluations `[1, t, t^2, ...]`,
+  /// as produced by `evals`, e.g., when they come from external data.
+  ///
+  /// Checks that `evals[0] == 1` and `evals[1] == t`, and spot-checks `evals[i] == evals[i-1] * t`
+  /// at 10 random indices. Returns `NovaError::InconsistentInput` with the smallest failing index
+  /// found, or `NovaError::InvalidInputLength` if the length of `evals` is not a power of two
+  /// that is at least 2.
+  pub fn from_tau_and_evaluations(t: Scalar, evals: Vec<Scalar>) -> Result<Self, NovaError> {
+    let n = evals.len();
+    if n < 2 || !n.is_power_of_two() {
+      return Err(NovaError::InvalidInputLength);
+    }
+
+    if evals[0] != Scalar::ONE {
+      return Err(NovaError::InconsistentInput { index: 0 });
+    }
+    if evals[1] != t {
+      return Err(NovaError::InconsistentInput { index: 1 });
+    }
+
+    let mut indices = (0..10)
+      .map(|_| 1 + (OsRng.next_u64() as usize) % (n - 1))
+      .collect::<Vec<_>>();
+    indices.sort_unstable();
+    if let Some(&index) = indices.iter().find(|&&i| evals[i] != evals[i - 1] * t) {
+      return Err(NovaError::InconsistentInput { index });
+    }
+
+    Ok(Self::new(&t, n.log_2()))
+  }
+
   /// Evaluates the `PowPolynomial` at all the `2^|t_pow|` points in its domain.
   ///
   /// Returns a vector of Scalars, each corresponding to the polynomial evaluation at a specific point.
-  #[cfg(test)]
   pub fn evals(&self) -> Vec<Scalar> {
     successors(Some(Scalar::ONE), |p| Some(*p * self.t_pow[0]))
       .take(1 << self.t_pow.len())
       .collect::<Vec<_>>()
   }
 
+  /// Evaluates the multilinear extension of the `PowPolynomial` at an arbitrary point `x`.
+  ///
+  /// Uses the product formula, where `x[0]` binds the top variable, which takes `O(ell)` work.
+  /// The result is the same as `MultilinearPolynomial::new(self.evals()).evaluate(x)`.
+  ///
+  /// Returns `NovaError::InvalidInputLength` if `x` does not have `ell` entries.
+  pub fn evaluate_multilinear(&self, x: &[Scalar]) -> Result<Scalar, NovaError> {
+    if x.len() != self.t_pow.len() {
+      return Err(NovaError::InvalidInputLength);
+    }
+
+    Ok(
+      x.iter()
+        .zip(self.t_pow.iter().rev())
+        .fold(Scalar::ONE, |acc, (x_i, t_i)| {
+          acc * (Scalar::ONE + (*t_i - Scalar::ONE) * x_i)
+        }),
+    )
+  }
+
+  /// Returns the coefficient of the monomial `prod_{k in S} x_k` of the multilinear extension,
+  /// where bit `k` of `S` selects the variable that carries `t^{2^k}`, as in `evals`.
+  ///
+  /// Expanding the product form gives `prod_{k in S} (t^{2^k} - 1)`, computed in `O(ell)` without
+  /// building the evaluation table. Bits of `S` at or above `ell` select no variable,
+  /// so the coefficient is zero.
+  pub fn coefficient_in_monomial_basis(&self, S: usize) -> Scalar {
+    if S >> self.t_pow.len() != 0 {
+      return Scalar::ZERO;
+    }
+
+    self
+      .t_pow
+      .iter()
+      .enumerate()
+      .filter(|(k, _)| (S >> k) & 1 == 1)
+      .fold(Scalar::ONE, |acc, (_, t)| acc * (*t - Scalar::ONE))
+  }
+
+  /// Evaluates `self(x) * other(y)` over the combined Boolean hypercube `{0,1}^(ell1 + ell2)`,
+  /// where `x` are the `ell1` variables of `self` and `y` the `ell2` variables of `other`.
+  ///
+  /// The result is in row-major order, i.e., entry `i * 2^ell2 + j` is `self.evals()[i] * other.evals()[j]`.
+  pub fn tensor_product(&self, other: &PowPolynomial<Scalar>) -> Vec<Scalar> {
+    let (evals1, evals2) = (self.evals(), other.evals());
+    evals1
+      .iter()
+      .flat_map(|e1| evals2.iter().map(move |e2| *e1 * e2))
+      .collect::<Vec<_>>()
+  }
+
   /// Computes two vectors such that their outer product equals the output of the `evals` function.
   /// This code ensures
   pub fn split_evals(&self, len_left: usize, len_right: usize) -> Vec<Scalar> {
@@ -63,12 +156,51 @@ impl<Scalar: PrimeField> PowPolynomial<Scalar> {
 
     [left, right].concat()
   }
+
+  /// Spot-checks that `evals` has the form produced by `split_evals` for `t`, i.e.,
+  /// `evals[i] = t^i` for `i < len_left` and `evals[len_left + j] = t^{j * len_left}`.
+  ///
+  /// Checks 5 random indices and returns `NovaError::InconsistentInput` with the smallest failing
+  /// index found, or `NovaError::InvalidInputLength` if `evals` does not have `len_left + len_right` entries.
+  pub fn verify_split_evals(
+    t: &Scalar,
+    evals: &[Scalar],
+    len_left: usize,
+    len_right: usize,
+  ) -> Result<(), NovaError> {
+    let n = len_left + len_right;
+    if evals.len() != n || len_left == 0 {
+      return Err(NovaError::InvalidInputLength);
+    }
+
+    let mut indices = (0..5)
+      .map(|_| (OsRng.next_u64() as usize) % n)
+      .collect::<Vec<_>>();
+    indices.sort_unstable();
+
+    let expected = |i: usize| {
+      let exp = if i < len_left {
+        i
+      } else {
+        (i - len_left) * len_left
+      };
+      t.pow_vartime([exp as u64])
+    };
+    if let Some(&index) = indices.iter().find(|&&i| evals[i] != expected(i)) {
+      return Err(NovaError::InconsistentInput { index });
+    }
+
+    Ok(())
+  }
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
-  use crate::provider::{bn256_grumpkin::bn256, pasta::pallas, secp_secq::secp256k1};
+  use crate::{
+    provider::{bn256_grumpkin::bn256, pasta::pallas, secp_secq::secp256k1},
+    spartan::polys::multilinear::MultilinearPolynomial,
+  };
   use rand::rngs::OsRng;
 
   fn test_evals_with<Scalar: PrimeField>() {
@@ -135,10 +267,185 @@ mod tests {
     }
   }
 
+  fn test_evaluate_multilinear_with<Scalar: PrimeField>() {
+    let t = Scalar::random(&mut OsRng);
+    let ell = 4;
+    let pow = PowPolynomial::new(&t, ell);
+
+    let x = (0..ell)
+      .map(|_| Scalar::random(&mut OsRng))
+      .collect::<Vec<_>>();
+    let eval = pow.evaluate_multilinear(&x).unwrap();
+
+    // check against the dense multilinear representation
+    let expected = MultilinearPolynomial::new(pow.evals()).evaluate(&x);
+    assert_eq!(eval, expected);
+
+    // the evaluation at a Boolean point recovers the corresponding power of t
+    let x = [Scalar::ZERO, Scalar::ONE, Scalar::ZERO, Scalar::ONE];
+    assert_eq!(pow.evaluate_multilinear(&x).unwrap(), pow.evals()[5]);
+
+    assert_eq!(
+      pow.evaluate_multilinear(&x[1..]),
+      Err(NovaError::InvalidInputLength)
+    );
+  }
+
+  #[test]
+  fn test_evaluate_multilinear() {
+    test_evaluate_multilinear_with::<bn256::Scalar>();
+    test_evaluate_multilinear_with::<pallas::Scalar>();
+    test_evaluate_multilinear_with::<secp256k1::Scalar>();
+  }
+
   #[test]
   fn test_split_evals() {
     test_split_evals_with::<bn256::Scalar>();
     test_split_evals_with::<pallas::Scalar>();
     test_split_evals_with::<secp256k1::Scalar>();
   }
+
+  fn test_from_tau_and_evaluations_with<Scalar: PrimeField>() {
+    let t = Scalar::random(&mut OsRng);
+    let ell = 4;
+    let evals = PowPolynomial::new(&t, ell).evals();
+
+    let pow = PowPolynomial::from_tau_and_evaluations(t, evals.clone()).unwrap();
+    assert_eq!(pow.evals(), evals);
+
+    let mut bad = evals.clone();
+    bad[0] = Scalar::ZERO;
+    assert_eq!(
+      PowPolynomial::from_tau_and_evaluations(t, bad).err(),
+      Some(NovaError::InconsistentInput { index: 0 })
+    );
+
+    let mut bad = evals.clone();
+    bad[1] += Scalar::ONE;
+    assert_eq!(
+      PowPolynomial::from_tau_and_evaluations(t, bad).err(),
+      Some(NovaError::InconsistentInput { index: 1 })
+    );
+
+    // break the ratio between every pair of consecutive entries beyond the first two
+    let mut bad = evals.clone();
+    let mut scale = Scalar::ONE;
+    for e in bad.iter_mut().skip(2) {
+      scale = scale.double();
+      *e *= scale;
+    }
+    assert!(matches!(
+      PowPolynomial::from_tau_and_evaluations(t, bad),
+      Err(NovaError::InconsistentInput { index }) if index >= 2
+    ));
+
+    assert_eq!(
+      PowPolynomial::from_tau_and_evaluations(t, evals[..5].to_vec()).err(),
+      Some(NovaError::InvalidInputLength)
+    );
+  }
+
+  #[test]
+  fn test_from_tau_and_evaluations() {
+    test_from_tau_and_evaluations_with::<bn256::Scalar>();
+    test_from_tau_and_evaluations_with::<pallas::Scalar>();
+    test_from_tau_and_evaluations_with::<secp256k1::Scalar>();
+  }
+
+  fn test_verify_split_evals_with<Scalar: PrimeField>() {
+    let t = Scalar::random(&mut OsRng);
+    let ell = 5;
+    let (len_left, len_right) = (1 << 3, 1 << 2);
+    let split_evals = PowPolynomial::new(&t, ell).split_evals(len_left, len_right);
+
+    assert!(PowPolynomial::verify_split_evals(&t, &split_evals, len_left, len_right).is_ok());
+
+    // corrupting every entry is detected at any sampled index
+    let bad = split_evals.iter().map(|e| e.double()).collect::<Vec<_>>();
+    assert!(matches!(
+      PowPolynomial::verify_split_evals(&t, &bad, len_left, len_right),
+      Err(NovaError::InconsistentInput { .. })
+    ));
+
+    assert_eq!(
+      PowPolynomial::verify_split_evals(&t, &split_evals[1..], len_left, len_right),
+      Err(NovaError::InvalidInputLength)
+    );
+  }
+
+  #[test]
+  fn test_verify_split_evals() {
+    test_verify_split_evals_with::<bn256::Scalar>();
+    test_verify_split_evals_with::<pallas::Scalar>();
+    test_verify_split_evals_with::<secp256k1::Scalar>();
+  }
+
+  fn test_tensor_product_with<Scalar: PrimeField>() {
+    let (t1, t2) = (Scalar::random(&mut OsRng), Scalar::random(&mut OsRng));
+    let (ell1, ell2) = (3, 2);
+    let (pow1, pow2) = (PowPolynomial::new(&t1, ell1), PowPolynomial::new(&t2, ell2));
+
+    let tensor = pow1.tensor_product(&pow2);
+    assert_eq!(tensor.len(), 1 << (ell1 + ell2));
+
+    // the result is the outer product of the two evaluation tables
+    let (evals1, evals2) = (pow1.evals(), pow2.evals());
+    for (i, e1) in evals1.iter().enumerate() {
+      for (j, e2) in evals2.iter().enumerate() {
+        assert_eq!(tensor[i * evals2.len() + j], *e1 * e2);
+      }
+    }
+  }
+
+  #[test]
+  fn test_tensor_product() {
+    test_tensor_product_with::<bn256::Scalar>();
+    test_tensor_product_with::<pallas::Scalar>();
+    test_tensor_product_with::<secp256k1::Scalar>();
+  }
+
+  fn test_random_with<Scalar: PrimeField>() {
+    let ell = 4;
+    let (pow, r) = PowPolynomial::<Scalar>::random_with_blinding(ell, &mut OsRng);
+    assert_ne!(r, Scalar::ZERO);
+
+    // the evaluations are the powers of the sampled t
+    let evals = pow.evals();
+    assert_eq!(evals.len(), 1 << ell);
+    assert!(PowPolynomial::from_tau_and_evaluations(pow.t_pow[0], evals).is_ok());
+  }
+
+  #[test]
+  fn test_random() {
+    test_random_with::<bn256::Scalar>();
+    test_random_with::<pallas::Scalar>();
+    test_random_with::<secp256k1::Scalar>();
+  }
+
+  fn test_coefficient_in_monomial_basis_with<Scalar: PrimeField>() {
+    let ell = 4;
+    let pow = PowPolynomial::new(&Scalar::random(&mut OsRng), ell);
+
+    // the Mobius transform of the evaluations yields the monomial coefficients
+    let mut coeffs = pow.evals();
+    for k in 0..ell {
+      for S in 0..(1 << ell) {
+        if (S >> k) & 1 == 1 {
+          coeffs[S] = coeffs[S] - coeffs[S ^ (1 << k)];
+        }
+      }
+    }
+
+    for (S, coeff) in coeffs.iter().enumerate() {
+      assert_eq!(pow.coefficient_in_monomial_basis(S), *coeff);
+    }
+    assert_eq!(pow.coefficient_in_monomial_basis(1 << ell), Scalar::ZERO);
+  }
+
+  #[test]
+  fn test_coefficient_in_monomial_basis() {
+    test_coefficient_in_monomial_basis_with::<bn256::Scalar>();
+    test_coefficient_in_monomial_basis_with::<pallas::Scalar>();
+    test_coefficient_in_monomial_basis_with::<secp256k1::Scalar>();
+  }
 }
@@ -1,7 +1,10 @@
 //! Main components:
 //! - `UniPoly`: an univariate dense polynomial in coefficient form (big endian),
 //! - `CompressedUniPoly`: a univariate dense polynomial, compressed (omitted linear term), in coefficient form (little endian),
-use crate::traits::{AbsorbInRO2Trait, Engine, Group, ROTrait, TranscriptReprTrait};
+use crate::{
+  errors::NovaError,
+  traits::{AbsorbInRO2Trait, Engine, Group, ROTrait, TranscriptReprTrait},
+};
 use core::panic;
 use ff::PrimeField;
 use rayon::prelude::{IntoParallelIterator, ParallelIterator};
@@ -9,6 +12,7 @@ use serde::{Deserialize, Serialize};
 
 // ax^2 + bx + c stored as vec![c, b, a]
 // ax^3 + bx^2 + cx + d stored as vec![d, c, b, a]
+/// A univariate polynomial in coefficient form, lowest degree first
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct UniPoly<Scalar: PrimeField> {
   pub(crate) coeffs: Vec<Scalar>,
@@ -22,6 +26,7 @@ pub struct CompressedUniPoly<Scalar: PrimeField> {
 }
 
 impl<Scalar: PrimeField> UniPoly<Scalar> {
+  /// Interpolates the polynomial whose evaluations at `0, 1, ..., n - 1` are `evals`
   pub fn from_evals(evals: &[Scalar]) -> Self {
     let n = evals.len();
     let xs: Vec<Scalar> = (0..n).map(|x| Scalar::from(x as u64)).collect();
@@ -43,14 +48,17 @@ impl<Scalar: PrimeField> UniPoly<Scalar> {
     Self { coeffs }
   }
 
+  /// Returns the degree of the polynomial
   pub fn degree(&self) -> usize {
     self.coeffs.len() - 1
   }
 
+  /// Evaluates the polynomial at zero
   pub fn eval_at_zero(&self) -> Scalar {
     self.coeffs[0]
   }
 
+  /// Evaluates the polynomial at one
   pub fn eval_at_one(&self) -> Scalar {
     (0..self.coeffs.len())
       .into_par_iter()
@@ -58,6 +66,7 @@ impl<Scalar: PrimeField> UniPoly<Scalar> {
       .sum()
   }
 
+  /// Evaluates the polynomial at `r`
   pub fn evaluate(&self, r: &Scalar) -> Scalar {
     let mut eval = self.coeffs[0];
     let mut power = *r;
@@ -68,6 +77,27 @@ impl<Scalar: PrimeField> UniPoly<Scalar> {
     eval
   }
 
+  /// Verifies a single sum-check round given the round polynomial's evaluations at `0, 1, ..., d`.
+  ///
+  /// Checks that `round_poly(0) + round_poly(1) == claimed_sum` and returns the claim for the
+  /// next round, `round_poly(challenge)`, obtained by interpolating the evaluations.
+  pub fn verify_sum_check_round(
+    round_poly: &[Scalar],
+    claimed_sum: Scalar,
+    challenge: Scalar,
+  ) -> Result<Scalar, NovaError> {
+    if round_poly.len() < 2 {
+      return Err(NovaError::InvalidInputLength);
+    }
+
+    if round_poly[0] + round_poly[1] != claimed_sum {
+      return Err(NovaError::InvalidSumcheckProof);
+    }
+
+    Ok(Self::from_evals(round_poly).evaluate(&challenge))
+  }
+
+  /// Drops the linear term, which `CompressedUniPoly::decompress` recovers from a hint
   pub fn compress(&self) -> CompressedUniPoly<Scalar> {
     let coeffs_except_linear_term = [&self.coeffs[0..1], &self.coeffs[2..]].concat();
     assert_eq!(coeffs_except_linear_term.len() + 1, self.coeffs.len());
@@ -295,4 +325,32 @@ mod tests {
     test_from_evals_quartic_with::<bn256::Scalar>();
     test_from_evals_quartic_with::<secp256k1::Scalar>();
   }
+
+  fn test_verify_sum_check_round_with<F: PrimeField>() {
+    // polynomial is x^3 + 2x^2 + 3x + 1
+    let evals = vec![F::ONE, F::from(7), F::from(23), F::from(55)];
+    let claimed_sum = F::from(8);
+
+    let next_claim = UniPoly::verify_sum_check_round(&evals, claimed_sum, F::from(4)).unwrap();
+    assert_eq!(next_claim, F::from(109));
+
+    // a wrong claim must be rejected
+    assert!(matches!(
+      UniPoly::verify_sum_check_round(&evals, claimed_sum + F::ONE, F::from(4)),
+      Err(NovaError::InvalidSumcheckProof)
+    ));
+
+    // a round polynomial needs at least its evaluations at 0 and 1
+    assert!(matches!(
+      UniPoly::verify_sum_check_round(&evals[..1], F::ONE, F::from(4)),
+      Err(NovaError::InvalidInputLength)
+    ));
+  }
+
+  #[test]
+  fn test_verify_sum_check_round() {
+    test_verify_sum_check_round_with::<pallas::Scalar>();
+    test_verify_sum_check_round_with::<bn256::Scalar>();
+    test_verify_sum_check_round_with::<secp256k1::Scalar>();
+  }
 }
@@ -1,6 +1,7 @@
 //! This module defines a collection of traits that define the behavior of a commitment engine
 //! We require the commitment engine to provide a commitment to vectors with a single group element
 use crate::{
+  errors::NovaError,
   provider::ptau::PtauFileError,
   traits::{AbsorbInRO2Trait, AbsorbInROTrait, Engine, TranscriptReprTrait},
 };
@@ -8,6 +9,7 @@ use core::{
   fmt::Debug,
   ops::{Add, Mul, MulAssign},
 };
+use ff::Field;
 use rayon::iter::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator};
 use serde::{Deserialize, Serialize};
 
@@ -37,6 +39,14 @@ pub trait CommitmentTrait<E: Engine>:
 {
   /// Returns the coordinate representation of the commitment
   fn to_coordinates(&self) -> (E::Base, E::Base, bool);
+
+  /// Returns the commitment with the given coordinate representation,
+  /// or `None` if the coordinates do not describe a valid commitment.
+  ///
+  /// The default returns `None`, for commitments that cannot be rebuilt from their coordinates
+  fn from_coordinates(_x: E::Base, _y: E::Base, _is_infinity: bool) -> Option<Self> {
+    None
+  }
 }
 
 /// A trait that helps determine the length of a structure.
@@ -70,9 +80,43 @@ pub trait CommitmentEngineTrait<E: Engine>: Clone + Send + Sync {
   /// Extracts the blinding generator
   fn derand_key(ck: &Self::CommitmentKey) -> Self::DerandKey;
 
-  /// Commits to the provided vector using the provided generators and random blind
+  /// Commits to the provided vector using the provided generators and random blind.
+  /// Panics if `v` has more entries than `ck` has generators; see `try_commit`
   fn commit(ck: &Self::CommitmentKey, v: &[E::Scalar], r: &E::Scalar) -> Self::Commitment;
 
+  /// Commits as in `commit`, but returns `NovaError::CommitmentKeyTooSmall` instead of panicking
+  /// if `v` has more entries than `ck` has generators, as reported by `Len::length`
+  fn try_commit(
+    ck: &Self::CommitmentKey,
+    v: &[E::Scalar],
+    r: &E::Scalar,
+  ) -> Result<Self::Commitment, NovaError> {
+    if v.len() > ck.length() {
+      return Err(NovaError::CommitmentKeyTooSmall {
+        required: v.len(),
+        available: ck.length(),
+      });
+    }
+    Ok(Self::commit(ck, v, r))
+  }
+
+  /// Returns the commitment to the all-zeros vector with blind zero, which is the identity of the
+  /// commitment group. It is obtained without an MSM, unlike calling `commit` on zeros.
+  fn commitment_to_zero() -> Self::Commitment {
+    Self::Commitment::default()
+  }
+
+  /// Computes the multi-scalar multiplication `Σ scalars[i] * G[i]` over the generators of `ck`,
+  /// i.e., an unblinded commitment to `scalars`.
+  ///
+  /// Returns `NovaError::CommitmentKeyTooSmall` if there are more scalars than generators in `ck`.
+  fn multi_scalar_mul(
+    ck: &Self::CommitmentKey,
+    scalars: &[E::Scalar],
+  ) -> Result<Self::Commitment, NovaError> {
+    Self::try_commit(ck, scalars, &E::Scalar::ZERO)
+  }
+
   /// Batch commits to the provided vectors using the provided generators and random blind
   fn batch_commit(
     ck: &Self::CommitmentKey,
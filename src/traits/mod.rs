@@ -6,7 +6,10 @@ use crate::{
 use core::fmt::Debug;
 use ff::{PrimeField, PrimeFieldBits};
 use num_bigint::BigInt;
+use rand_chacha::ChaCha20Rng;
+use rand_core::{RngCore, SeedableRng};
 use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
 
 pub mod commitment;
 
@@ -149,6 +152,31 @@ pub trait TranscriptEngineTrait<E: Engine>: Send + Sync {
 
   /// adds a domain separator
   fn dom_sep(&mut self, bytes: &'static [u8]);
+
+  /// absorbs raw bytes under a label, e.g., a session identifier or an application-specific tag;
+  /// the bytes are prefixed with their length, so consecutive calls cannot be confused with one another
+  fn absorb_bytes(&mut self, label: &'static [u8], bytes: &[u8]) {
+    self.absorb(label, &RawBytes(bytes));
+  }
+
+  /// fills `output` with pseudorandom bytes derived from the transcript state, e.g., to sample a
+  /// permutation; a domain separator keeps the squeezed seed distinct from `squeeze` under the same label
+  fn squeeze_bytes(&mut self, label: &'static [u8], output: &mut [u8]) -> Result<(), NovaError> {
+    self.dom_sep(b"squeeze_bytes");
+    let seed = self.squeeze(label)?;
+    let mut rng = ChaCha20Rng::from_seed(Sha3_256::digest(seed.to_repr().as_ref()).into());
+    rng.fill_bytes(output);
+    Ok(())
+  }
+}
+
+/// Raw bytes absorbed by `TranscriptEngineTrait::absorb_bytes`
+struct RawBytes<'a>(&'a [u8]);
+
+impl<G: Group> TranscriptReprTrait<G> for RawBytes<'_> {
+  fn to_transcript_bytes(&self) -> Vec<u8> {
+    [&(self.0.len() as u64).to_le_bytes(), self.0].concat()
+  }
 }
 
 /// Defines additional methods on `PrimeField` objects